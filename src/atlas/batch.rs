@@ -0,0 +1,90 @@
+use std::hash::Hash;
+
+use graphics::{
+    math::Matrix2d,
+    triangulation::{rect_tri_list_uv, rect_tri_list_xy},
+    DrawState, Graphics, BACK_END_MAX_VERTEX_COUNT,
+};
+use opengl_graphics::GlGraphics;
+
+use super::Atlas;
+
+/// Vertices handed to the back end at once: as many whole quads as it takes.
+const CHUNK: usize = BACK_END_MAX_VERTEX_COUNT / 6 * 6;
+
+/// Collects images from an atlas over a frame, to submit them to the GPU
+/// together rather than one draw call each.
+pub struct SpriteBatch<'a, K> {
+    atlas: &'a Atlas<K>,
+    vertices: Vec<[f32; 2]>,
+    uvs: Vec<[f32; 2]>,
+    /// Consecutive quads drawn from the same page in the same color, which
+    /// can share a call.
+    runs: Vec<Run>,
+}
+
+struct Run {
+    page: usize,
+    color: [f32; 4],
+    /// One past the run's last vertex.
+    end: usize,
+}
+
+impl<'a, K: Eq + Hash> SpriteBatch<'a, K> {
+    pub fn new(atlas: &'a Atlas<K>) -> Self {
+        Self {
+            atlas,
+            vertices: Vec::new(),
+            uvs: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Queues the image for the key, stretched over the rectangle, tinted by
+    /// the color. Keys missing from the atlas are skipped.
+    pub fn push(&mut self, key: &K, rect: [f64; 4], color: [f32; 4], transform: Matrix2d) {
+        let region = match self.atlas.region(key) {
+            Some(region) => region,
+            None => return,
+        };
+        let page = self.atlas.page(region.page);
+        self.vertices
+            .extend_from_slice(&rect_tri_list_xy(transform, rect));
+        self.uvs
+            .extend_from_slice(&rect_tri_list_uv(page, region.src_rect()));
+
+        let end = self.vertices.len();
+        match self.runs.last_mut() {
+            Some(run) if run.page == region.page && run.color == color => run.end = end,
+            _ => self.runs.push(Run {
+                page: region.page,
+                color,
+                end,
+            }),
+        }
+    }
+
+    /// The number of images queued.
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 6
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Draws everything queued, in the order it was queued.
+    pub fn draw(&self, draw_state: &DrawState, g: &mut GlGraphics) {
+        let mut start = 0;
+        for run in &self.runs {
+            let vertices = &self.vertices[start..run.end];
+            let uvs = &self.uvs[start..run.end];
+            g.tri_list_uv(draw_state, &run.color, self.atlas.page(run.page), |f| {
+                for (vertices, uvs) in vertices.chunks(CHUNK).zip(uvs.chunks(CHUNK)) {
+                    f(vertices, uvs);
+                }
+            });
+            start = run.end;
+        }
+    }
+}
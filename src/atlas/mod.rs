@@ -0,0 +1,49 @@
+mod batch;
+mod packer;
+
+use std::{collections::HashMap, error::Error, hash::Hash};
+
+use graphics::{math::Matrix2d, DrawState, Image};
+use opengl_graphics::{GlGraphics, Texture, TextureSettings};
+
+pub use batch::SpriteBatch;
+pub use packer::{Packed, Packer, Region};
+
+/// Many images packed onto a few textures, so that they can be drawn together
+/// without switching textures in between.
+pub struct Atlas<K> {
+    pages: Vec<Texture>,
+    regions: HashMap<K, Region>,
+}
+
+impl<K: Eq + Hash> Atlas<K> {
+    /// Packs the packer's images and uploads the pages.
+    pub fn new(packer: Packer<K>) -> Result<Self, Box<dyn Error>> {
+        let Packed { pages, regions } = packer.pack()?;
+        let pages = pages
+            .iter()
+            .map(|page| Texture::from_image(page, &TextureSettings::new()))
+            .collect();
+        Ok(Self { pages, regions })
+    }
+
+    /// Where the image for the key was packed, if it was.
+    pub fn region(&self, key: &K) -> Option<Region> {
+        self.regions.get(key).copied()
+    }
+
+    pub fn page(&self, index: usize) -> &Texture {
+        &self.pages[index]
+    }
+
+    /// Draws a single image, at its own size, with its top-left corner at
+    /// the transform's origin. Many images are better drawn with a batch.
+    pub fn draw(&self, key: &K, draw_state: &DrawState, transform: Matrix2d, g: &mut GlGraphics) {
+        if let Some(region) = self.region(key) {
+            Image::new()
+                .src_rect(region.src_rect())
+                .rect([0.0, 0.0, region.width as f64, region.height as f64])
+                .draw(self.page(region.page), draw_state, transform, g);
+        }
+    }
+}
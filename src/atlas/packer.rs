@@ -0,0 +1,240 @@
+use std::{cmp::Reverse, collections::HashMap, error::Error, hash::Hash};
+
+use image::{GenericImage, RgbaImage};
+
+/// Transparent pixels left around each image, so that neighbours don't bleed
+/// into one another when the page is filtered.
+const PADDING: u32 = 1;
+
+/// Where an image was packed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub page: usize,
+    /// The image's rectangle on its page, in pixels.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    /// The region as a source rectangle for drawing from its page.
+    pub fn src_rect(&self) -> [f64; 4] {
+        [
+            self.x as f64,
+            self.y as f64,
+            self.width as f64,
+            self.height as f64,
+        ]
+    }
+}
+
+/// Gathers images to pack together onto as few square pages as they fit.
+pub struct Packer<K> {
+    page_size: u32,
+    images: Vec<(K, RgbaImage)>,
+}
+
+/// The pages images were packed onto, and where each image went.
+pub struct Packed<K> {
+    pub pages: Vec<RgbaImage>,
+    pub regions: HashMap<K, Region>,
+}
+
+/// The page being packed, which is filled in rows, or shelves, from the top.
+struct Shelf {
+    y: u32,
+    height: u32,
+    /// Where the next image on the shelf goes.
+    x: u32,
+}
+
+impl<K: Eq + Hash> Packer<K> {
+    /// A packer for pages of the given width and height.
+    pub fn new(page_size: u32) -> Self {
+        Self {
+            page_size,
+            images: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, key: K, image: RgbaImage) {
+        self.images.push((key, image));
+    }
+
+    /// Lays the images out on pages, tallest first, so that each shelf wastes
+    /// little height.
+    pub fn pack(mut self) -> Result<Packed<K>, Box<dyn Error>> {
+        let size = self.page_size;
+        self.images
+            .sort_by_key(|(_, image)| Reverse(image.height()));
+
+        let mut pages: Vec<RgbaImage> = Vec::new();
+        let mut regions = HashMap::new();
+        let mut shelf = Shelf {
+            y: PADDING,
+            height: 0,
+            x: PADDING,
+        };
+        for (key, image) in self.images {
+            let (width, height) = image.dimensions();
+            if width + 2 * PADDING > size || height + 2 * PADDING > size {
+                return Err(format!(
+                    "a {}x{} image does not fit on a {}x{} page",
+                    width, height, size, size
+                )
+                .into());
+            }
+
+            if shelf.x + width + PADDING > size {
+                shelf = Shelf {
+                    y: shelf.y + shelf.height + PADDING,
+                    height: 0,
+                    x: PADDING,
+                };
+            }
+            if pages.is_empty() || shelf.y + height + PADDING > size {
+                pages.push(RgbaImage::new(size, size));
+                shelf = Shelf {
+                    y: PADDING,
+                    height: 0,
+                    x: PADDING,
+                };
+            }
+
+            let page = pages.len() - 1;
+            pages[page].copy_from(&image, shelf.x, shelf.y)?;
+            regions.insert(
+                key,
+                Region {
+                    page,
+                    x: shelf.x,
+                    y: shelf.y,
+                    width,
+                    height,
+                },
+            );
+            shelf.x += width + PADDING;
+            shelf.height = shelf.height.max(height);
+        }
+
+        Ok(Packed { pages, regions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+
+    /// An image filled with a color made from the key, so that it can be
+    /// found again on its page.
+    fn image(key: u8, width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba([key, 0, 0, 255]))
+    }
+
+    fn pack(page_size: u32, sizes: &[(u32, u32)]) -> Packed<u8> {
+        let mut packer = Packer::new(page_size);
+        for (key, &(width, height)) in sizes.iter().enumerate() {
+            packer.add(key as u8, image(key as u8, width, height));
+        }
+        packer.pack().unwrap()
+    }
+
+    #[test]
+    fn refuses_images_larger_than_a_page() {
+        let mut packer = Packer::new(64);
+        packer.add(0, image(0, 16, 16));
+        packer.add(1, image(1, 64, 8));
+        assert!(packer.pack().is_err());
+
+        // Padding counts towards the size, too.
+        let mut packer = Packer::new(64);
+        packer.add(0, image(0, 8, 63));
+        assert!(packer.pack().is_err());
+    }
+
+    #[test]
+    fn fills_shelves_then_pages() {
+        // Three fit side by side on a shelf, and two shelves on a page.
+        let packed = pack(64, &[(20, 30); 7]);
+        let mut regions: Vec<_> = packed.regions.values().collect();
+        regions.sort_by_key(|region| (region.page, region.y, region.x));
+
+        let shelf = |region: &Region| (region.page, region.y);
+        assert_eq!(packed.pages.len(), 2);
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| shelf(region))
+                .collect::<Vec<_>>(),
+            [
+                (0, PADDING),
+                (0, PADDING),
+                (0, PADDING),
+                (0, 30 + 2 * PADDING),
+                (0, 30 + 2 * PADDING),
+                (0, 30 + 2 * PADDING),
+                (1, PADDING),
+            ]
+        );
+        assert_eq!(
+            regions.iter().map(|region| region.x).collect::<Vec<_>>(),
+            [
+                PADDING,
+                20 + 2 * PADDING,
+                40 + 3 * PADDING,
+                PADDING,
+                20 + 2 * PADDING,
+                40 + 3 * PADDING,
+                PADDING,
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_images_apart() {
+        let sizes: Vec<_> = (0..40u32)
+            .map(|i| (4 + i * 7 % 23, 3 + i * 11 % 29))
+            .collect();
+        let packed = pack(96, &sizes);
+        assert_eq!(packed.regions.len(), sizes.len());
+
+        let regions: Vec<_> = packed.regions.iter().collect();
+        for (i, (_, a)) in regions.iter().enumerate() {
+            // Each image keeps its padding from the edges of the page...
+            assert!(a.x >= PADDING && a.y >= PADDING);
+            assert!(a.x + a.width + PADDING <= 96 && a.y + a.height + PADDING <= 96);
+
+            // ...and from every other image on the page.
+            for (_, b) in &regions[i + 1..] {
+                let apart = a.page != b.page
+                    || a.x + a.width + PADDING <= b.x
+                    || b.x + b.width + PADDING <= a.x
+                    || a.y + a.height + PADDING <= b.y
+                    || b.y + b.height + PADDING <= a.y;
+                assert!(apart, "{:?} and {:?} are too close", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn copies_images_onto_their_pages() {
+        let sizes: Vec<_> = (0..12u32).map(|i| (5 + i % 4, 6 + i % 5)).collect();
+        let packed = pack(32, &sizes);
+
+        for (key, region) in &packed.regions {
+            let page = &packed.pages[region.page];
+            for y in region.y..region.y + region.height {
+                for x in region.x..region.x + region.width {
+                    assert_eq!(page.get_pixel(x, y)[0], *key);
+                    assert_eq!(page.get_pixel(x, y)[3], 255);
+                }
+            }
+            // The padding around it is left clear.
+            assert_eq!(page.get_pixel(region.x - 1, region.y)[3], 0);
+            assert_eq!(page.get_pixel(region.x, region.y - 1)[3], 0);
+        }
+    }
+}
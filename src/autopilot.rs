@@ -0,0 +1,186 @@
+use std::f32::consts::PI;
+
+use crate::{
+    bot::Observation,
+    component::{HitMask, Spatial},
+    controller::ControllerState,
+};
+
+/// Speed of the default weapon's bullets, which shots are led by.
+const BULLET_SPEED: f32 = 1_000.0;
+/// How far ahead to look for collisions, in seconds.
+const HORIZON: f32 = 0.6;
+/// Room to leave between the ship and anything it dodges.
+const CLEARANCE: f32 = 60.0;
+/// Targets further away than this aren't worth shooting at.
+const FIRING_RANGE: f32 = 600.0;
+/// The least a shot may be off target and still be taken, in radians, for
+/// targets too small or distant to be lined up on exactly.
+const AIM_TOLERANCE: f32 = 0.05;
+/// Fraction of the ship's speed shed each update when there is nothing to
+/// dodge, so that it doesn't drift into trouble.
+const BRAKING: f32 = 0.5;
+
+/// Plays the game by itself, from the same observations as a bot. It dodges
+/// whatever is about to hit it, aims where the nearest threat is going to be,
+/// and fires whenever the shot is lined up.
+pub struct Autopilot {
+    seat: u8,
+}
+
+/// Something the autopilot dodges and shoots at.
+struct Threat {
+    spatial: Spatial,
+    radius: f32,
+}
+
+impl Autopilot {
+    /// An autopilot for the ship in the given seat.
+    pub fn new(seat: u8) -> Self {
+        Self { seat }
+    }
+
+    pub fn seat(&self) -> u8 {
+        self.seat
+    }
+
+    pub fn control(&self, observation: &Observation) -> ControllerState {
+        let mut controller = ControllerState::new();
+        let ship = match observation.players.iter().find(|p| p.seat == self.seat) {
+            Some(ship) => ship.spatial,
+            None => return controller,
+        };
+
+        // Other players' ships are threats too, in versus.
+        let threats: Vec<_> = observation
+            .asteroids
+            .iter()
+            .map(|rock| (rock.spatial, rock.hit_mask))
+            .chain(
+                observation
+                    .players
+                    .iter()
+                    .filter(|p| p.seat != self.seat)
+                    .map(|p| (p.spatial, p.hit_mask)),
+            )
+            .map(|(spatial, mask)| Threat {
+                spatial,
+                radius: match mask {
+                    HitMask::Circle { radius } => radius,
+                    HitMask::Point => 0.0,
+                },
+            })
+            .collect();
+
+        controller.left_thumb = match dodge(&ship, &threats) {
+            Some((x, y)) => (x as f64, y as f64),
+            None => brake(&ship),
+        };
+
+        let target = threats
+            .iter()
+            .filter_map(|threat| aim(&ship, threat).map(|angle| (angle, threat)))
+            .min_by(|(_, a), (_, b)| {
+                distance(&ship, &a.spatial).total_cmp(&distance(&ship, &b.spatial))
+            });
+        if let Some((angle, threat)) = target {
+            let (sin, cos) = angle.sin_cos();
+            controller.right_thumb = (cos as f64, sin as f64);
+
+            // The ship turns as soon as it is told to, so it is pointing where
+            // it was aimed last update.
+            let range = distance(&ship, &threat.spatial);
+            let tolerance = (threat.radius / range.max(threat.radius)).asin();
+            if range < FIRING_RANGE
+                && angle_between(ship.angle_o, angle) < tolerance.max(AIM_TOLERANCE)
+            {
+                controller.press_fire();
+            }
+        }
+
+        controller
+    }
+}
+
+/// Which way to thrust to get out of the way of the most urgent threat, if any
+/// is on course to hit the ship.
+fn dodge(ship: &Spatial, threats: &[Threat]) -> Option<(f32, f32)> {
+    threats
+        .iter()
+        .filter_map(|threat| {
+            // Follow the threat relative to the ship, to where the two will
+            // come closest.
+            let (px, py) = (threat.spatial.x - ship.x, threat.spatial.y - ship.y);
+            let (vx, vy) = (threat.spatial.dx - ship.dx, threat.spatial.dy - ship.dy);
+            let speed_squared = vx * vx + vy * vy;
+            let when = if speed_squared > 0.0 {
+                (-(px * vx + py * vy) / speed_squared).clamp(0.0, HORIZON)
+            } else {
+                0.0
+            };
+            let (cx, cy) = (px + vx * when, py + vy * when);
+            let miss = cx.hypot(cy);
+            if miss > threat.radius + CLEARANCE {
+                return None;
+            }
+
+            // Move away from where the threat will be. If it is coming
+            // straight at the ship, move across its path instead.
+            let away = if miss > 1.0 {
+                (-cx / miss, -cy / miss)
+            } else {
+                let speed = speed_squared.sqrt().max(1.0);
+                (-vy / speed, vx / speed)
+            };
+            Some((when, away))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, away)| away)
+}
+
+/// Thrust against the ship's drift.
+fn brake(ship: &Spatial) -> (f64, f64) {
+    let speed = ship.dx.hypot(ship.dy);
+    if speed < 1.0 {
+        return (0.0, 0.0);
+    }
+    (
+        (-ship.dx / speed * BRAKING) as f64,
+        (-ship.dy / speed * BRAKING) as f64,
+    )
+}
+
+/// The direction to fire in to hit the threat where it will be when the shot
+/// arrives, if a shot can catch it at all.
+fn aim(ship: &Spatial, threat: &Threat) -> Option<f32> {
+    let (px, py) = (threat.spatial.x - ship.x, threat.spatial.y - ship.y);
+    let (vx, vy) = (threat.spatial.dx, threat.spatial.dy);
+    // Solve |p + v t| = BULLET_SPEED t for the earliest positive t.
+    let a = vx * vx + vy * vy - BULLET_SPEED * BULLET_SPEED;
+    let b = 2.0 * (px * vx + py * vy);
+    let c = px * px + py * py;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let t = [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+        .iter()
+        .copied()
+        .filter(|t| *t > 0.0)
+        .fold(f32::INFINITY, f32::min);
+    if !t.is_finite() {
+        return None;
+    }
+    Some((py + vy * t).atan2(px + vx * t))
+}
+
+fn distance(a: &Spatial, b: &Spatial) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+/// The smallest angle between two directions.
+fn angle_between(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(2.0 * PI);
+    difference.min(2.0 * PI - difference)
+}
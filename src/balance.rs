@@ -0,0 +1,68 @@
+use std::sync::{mpsc, Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::{
+    autopilot::Autopilot,
+    bot::{self, Observation},
+    resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+    scene::{SceneEvent, Simulation},
+};
+
+/// How a game flown by the autopilot went.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Trial {
+    pub score: u32,
+    /// How long the ship lasted, in seconds of game time.
+    pub survived: f32,
+    pub shots: u32,
+    pub hits: u32,
+    /// The fraction of shots which struck something, or zero if none were
+    /// fired.
+    pub accuracy: f32,
+    pub asteroids_destroyed: u32,
+    /// Whether the game ended by itself, rather than being cut short.
+    pub is_over: bool,
+}
+
+impl Trial {
+    fn of(sim: &Simulation, is_over: bool) -> Self {
+        let breakdown = sim.breakdown();
+        Self {
+            score: sim.score().points(),
+            survived: sim.replay.ticks() as f32 * bot::TIMESTEP.as_secs_f32(),
+            shots: breakdown.shots,
+            hits: breakdown.hits,
+            accuracy: breakdown.accuracy().unwrap_or(0.0),
+            asteroids_destroyed: breakdown.asteroids,
+            is_over,
+        }
+    }
+}
+
+/// Plays a game without a window, with the autopilot at the controls, until it
+/// is over or has run for the given number of updates.
+pub fn trial(mode: GameMode, tuning: Tuning, seed: u64, max_ticks: u64) -> Trial {
+    let (sender, events) = mpsc::channel();
+    let mut sim = Simulation::new(
+        Bounds::new(0.0, 0.0, 800.0, 600.0),
+        tuning,
+        mode,
+        seed,
+        Arc::new(Mutex::new(sender)),
+    );
+    let autopilot = Autopilot::new(0);
+
+    while sim.replay.ticks() < max_ticks {
+        let controller = autopilot.control(&Observation::of(&sim));
+        sim.step(bot::TIMESTEP, &[controller]);
+
+        let is_over = events
+            .try_iter()
+            .any(|event| matches!(event, SceneEvent::GameOver { .. }));
+        if is_over {
+            return Trial::of(&sim, true);
+        }
+    }
+    Trial::of(&sim, false)
+}
@@ -0,0 +1,238 @@
+//! Plays many games without a window, flown by the autopilot, across a grid of
+//! tuning parameters and seeds, and reports how each went.
+//!
+//! Usage: `balance [<option>...]`, where the options are:
+//!
+//! - `--mode <mode>`: the mode to play, `Classic` by default.
+//! - `--seeds <count>`: games to play at each point of the grid, with seeds
+//!   counting up from zero. 10 by default.
+//! - `--minutes <minutes>`: game time after which a game is cut short. 10 by
+//!   default.
+//! - `--asteroids <list>`, `--asteroid-speed <list>`, `--fire-rate <list>`:
+//!   comma-separated values of each parameter to try, such as `0.5,1,2`. Each
+//!   is left as the game has it by default.
+//! - `--json`: write one JSON object per game instead of CSV.
+//!
+//! Games are written to standard output, one per line, in grid order.
+
+use std::{
+    env,
+    io::{self, Write},
+    process,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use serde::Serialize;
+
+use rust_rocks::{
+    balance::{self, Trial},
+    bot,
+    resource::{
+        game_mode::GameMode,
+        tuning::{Tuning, ASTEROID_COUNTS, ASTEROID_SPEEDS, FIRE_RATES},
+    },
+};
+
+/// The conditions a game was played under, and how it went.
+#[derive(Serialize)]
+struct Row {
+    mode: GameMode,
+    asteroids: usize,
+    asteroid_speed: f32,
+    fire_rate: f32,
+    seed: u64,
+    #[serde(flatten)]
+    trial: Trial,
+}
+
+const CSV_HEADER: &str = "mode,asteroids,asteroid_speed,fire_rate,seed,score,survived,shots,\
+                          hits,accuracy,asteroids_destroyed,is_over";
+
+struct Options {
+    mode: GameMode,
+    seeds: u64,
+    minutes: f32,
+    asteroids: Vec<usize>,
+    asteroid_speeds: Vec<f32>,
+    fire_rates: Vec<f32>,
+    is_json: bool,
+}
+
+fn main() {
+    env_logger::init();
+
+    let options = parse(env::args().skip(1).collect()).unwrap_or_else(|| usage());
+    let max_ticks = (options.minutes * 60.0 / bot::TIMESTEP.as_secs_f32()) as u64;
+
+    let mut grid = Vec::new();
+    for &asteroids in &options.asteroids {
+        for &asteroid_speed in &options.asteroid_speeds {
+            for &fire_rate in &options.fire_rates {
+                for seed in 0..options.seeds {
+                    let tuning = Tuning {
+                        asteroids,
+                        asteroid_speed,
+                        fire_rate,
+                        ..Tuning::new()
+                    };
+                    grid.push((tuning, seed));
+                }
+            }
+        }
+    }
+    let grid = Arc::new(grid);
+
+    // Simulations can't move between threads, so each worker plays whole
+    // games, taking the next from the grid as it finishes the last.
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, results) = mpsc::channel();
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    for _ in 0..workers.min(grid.len()) {
+        let (grid, next, sender) = (Arc::clone(&grid), Arc::clone(&next), sender.clone());
+        let mode = options.mode;
+        thread::spawn(move || loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let (tuning, seed) = match grid.get(i) {
+                Some(point) => *point,
+                None => return,
+            };
+            let trial = balance::trial(mode, tuning, seed, max_ticks);
+            if sender.send((i, trial)).is_err() {
+                return;
+            }
+        });
+    }
+    drop(sender);
+
+    let mut trials = vec![None; grid.len()];
+    for (played, (i, trial)) in results.into_iter().enumerate() {
+        trials[i] = Some(trial);
+        log::info!("Played {} of {} games", played + 1, grid.len());
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let written = write(&mut out, &options, &grid, trials);
+    if let Err(e) = written {
+        eprintln!("Could not write the results: {}", e);
+        process::exit(2);
+    }
+}
+
+fn write(
+    out: &mut impl Write,
+    options: &Options,
+    grid: &[(Tuning, u64)],
+    trials: Vec<Option<Trial>>,
+) -> io::Result<()> {
+    if !options.is_json {
+        writeln!(out, "{}", CSV_HEADER)?;
+    }
+    for (&(tuning, seed), trial) in grid.iter().zip(trials) {
+        let row = Row {
+            mode: options.mode,
+            asteroids: tuning.asteroids,
+            asteroid_speed: tuning.asteroid_speed,
+            fire_rate: tuning.fire_rate,
+            seed,
+            trial: trial.expect("every game is played"),
+        };
+        if options.is_json {
+            serde_json::to_writer(&mut *out, &row)?;
+            writeln!(out)?;
+        } else {
+            let trial = row.trial;
+            writeln!(
+                out,
+                "{:?},{},{},{},{},{},{},{},{},{},{},{}",
+                row.mode,
+                row.asteroids,
+                row.asteroid_speed,
+                row.fire_rate,
+                row.seed,
+                trial.score,
+                trial.survived,
+                trial.shots,
+                trial.hits,
+                trial.accuracy,
+                trial.asteroids_destroyed,
+                trial.is_over
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn parse(args: Vec<String>) -> Option<Options> {
+    let defaults = Tuning::new();
+    let mut options = Options {
+        mode: GameMode::Classic,
+        seeds: 10,
+        minutes: 10.0,
+        asteroids: vec![defaults.asteroids],
+        asteroid_speeds: vec![defaults.asteroid_speed],
+        fire_rates: vec![defaults.fire_rate],
+        is_json: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.is_json = true,
+            // Modes are named as they are in saves and replays, e.g. TimeAttack.
+            "--mode" => {
+                let mode = args.next()?;
+                options.mode = serde_json::from_value(mode.into()).ok()?;
+                if options.mode.seats() != 1 {
+                    return None;
+                }
+            }
+            "--seeds" => options.seeds = args.next()?.parse().ok()?,
+            "--minutes" => options.minutes = args.next()?.parse().ok()?,
+            "--asteroids" => options.asteroids = list(&args.next()?)?,
+            "--asteroid-speed" => options.asteroid_speeds = list(&args.next()?)?,
+            "--fire-rate" => options.fire_rates = list(&args.next()?)?,
+            _ => return None,
+        }
+    }
+    // Anything the simulation would clamp is refused, so that every row is
+    // played as it is labelled.
+    let is_valid = options.minutes.is_finite()
+        && options.minutes > 0.0
+        && options
+            .asteroids
+            .iter()
+            .all(|count| ASTEROID_COUNTS.contains(count))
+        && options
+            .asteroid_speeds
+            .iter()
+            .all(|speed| ASTEROID_SPEEDS.contains(speed))
+        && options
+            .fire_rates
+            .iter()
+            .all(|rate| FIRE_RATES.contains(rate));
+    if !is_valid {
+        return None;
+    }
+    Some(options)
+}
+
+/// Parses a comma-separated list of values.
+fn list<T: FromStr>(arg: &str) -> Option<Vec<T>> {
+    arg.split(',')
+        .map(|value| value.trim().parse().ok())
+        .collect()
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: balance [--mode <mode>] [--seeds <count>] [--minutes <minutes>] \
+         [--asteroids <list>] [--asteroid-speed <list>] [--fire-rate <list>] [--json]"
+    );
+    process::exit(2);
+}
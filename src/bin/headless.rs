@@ -0,0 +1,63 @@
+//! Plays games without a window, each flown by a bot connecting over TCP.
+//!
+//! Usage: `headless <address> [<mode>]`
+//!
+//! Each bot to connect plays one game of the given mode, `Classic` by default,
+//! after which the next bot may connect.
+
+use std::{env, net::TcpListener, process};
+
+use rust_rocks::{
+    bot::{self, Bot},
+    resource::game_mode::GameMode,
+};
+
+fn main() {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (addr, mode) = match args.as_slice() {
+        [addr] => (addr, GameMode::Classic),
+        // Modes are named as they are in saves and replays, e.g. TimeAttack.
+        [addr, mode] => match serde_json::from_value::<GameMode>(mode.as_str().into()) {
+            Ok(mode) if mode.seats() == 1 => (addr, mode),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Could not listen at {}: {}", addr, e);
+            process::exit(2);
+        }
+    };
+    println!("Waiting for bots at {}", addr);
+
+    loop {
+        let mut bot = match Bot::accept(&listener) {
+            Ok(bot) => bot,
+            Err(e) => {
+                eprintln!("Could not accept a bot: {}", e);
+                continue;
+            }
+        };
+
+        let seed = rand::random();
+        let outcome = bot::play(&mut bot, mode, seed);
+        println!(
+            "{} with seed {} scored {} in {} updates{}",
+            mode.name(),
+            seed,
+            outcome.score,
+            outcome.ticks,
+            if outcome.is_over { "" } else { " (bot left)" }
+        );
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: headless <address> [<mode>]");
+    process::exit(2);
+}
@@ -0,0 +1,68 @@
+//! Plays back a replay headlessly and reports its score, or checks it against a
+//! claimed score and end tick.
+//!
+//! Usage: `verify <replay.json> [<score> <ticks>]`
+//!
+//! Exits with status 1 if the claim does not match, and 2 if the replay could
+//! not be read.
+
+use std::{env, path::Path, process};
+
+use rust_rocks::{leaderboard::Run, replay::Replay, resource::score::Score, verify};
+
+fn main() {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (path, claim) = match args.as_slice() {
+        [path] => (path, None),
+        [path, score, ticks] => match (score.parse::<u32>(), ticks.parse::<u64>()) {
+            (Ok(score), Ok(ticks)) => (path, Some((Score::from(score), ticks))),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+
+    let replay = match Replay::load(Path::new(path)) {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", path, e);
+            process::exit(2);
+        }
+    };
+
+    match claim {
+        None => {
+            let outcome = verify::simulate(&replay);
+            println!(
+                "{} scored {} in {} updates{}",
+                replay.mode.name(),
+                outcome.score,
+                outcome.ticks,
+                if outcome.is_over { "" } else { " (unfinished)" }
+            );
+        }
+        Some((score, ticks)) => {
+            let claim = Run {
+                name: String::new(),
+                score,
+                seed: replay.seed,
+                mode: replay.mode,
+                replay: replay.hash(),
+                ticks,
+            };
+            match verify::verify(&replay, &claim) {
+                Ok(_) => println!("Verified: {} in {} updates", score, ticks),
+                Err(mismatch) => {
+                    println!("Not verified: {}", mismatch);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: verify <replay.json> [<score> <ticks>]");
+    process::exit(2);
+}
@@ -0,0 +1,246 @@
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
+
+use legion::IntoQuery;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    component::{Asteroid, HitMask, PlayerInput, Spatial},
+    controller::ControllerState,
+    resource::{bounds::Bounds, game_mode::GameMode, score::Score, tuning::Tuning},
+    scene::{SceneEvent, Simulation},
+    verify::Outcome,
+};
+
+/// Time between updates of a headless game, the same as the window's.
+pub const TIMESTEP: Duration = Duration::from_nanos(1_000_000_000 / 120);
+/// How long a bot may take to answer before it is taken to have left.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What a bot can see of the game before each update.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Observation {
+    /// The number of updates so far.
+    pub tick: u64,
+    pub score: Score,
+    pub players: Vec<Ship>,
+    pub asteroids: Vec<Rock>,
+    /// Whether the game is over, in which case this is the last observation
+    /// and no answer is expected.
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Ship {
+    /// The seat of the player flying the ship.
+    pub seat: u8,
+    pub spatial: Spatial,
+    pub hit_mask: HitMask,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Rock {
+    pub spatial: Spatial,
+    pub hit_mask: HitMask,
+}
+
+impl Observation {
+    pub fn of(sim: &Simulation) -> Self {
+        let players = <(&Spatial, &HitMask, &PlayerInput)>::query()
+            .iter(&sim.world)
+            .map(|(spatial, hit_mask, input)| Ship {
+                seat: input.seat,
+                spatial: *spatial,
+                hit_mask: *hit_mask,
+            })
+            .collect();
+        let asteroids = <(&Spatial, &HitMask, &Asteroid)>::query()
+            .iter(&sim.world)
+            .map(|(spatial, hit_mask, _)| Rock {
+                spatial: *spatial,
+                hit_mask: *hit_mask,
+            })
+            .collect();
+
+        Self {
+            tick: sim.replay.ticks(),
+            score: sim.score(),
+            players,
+            asteroids,
+            done: false,
+        }
+    }
+}
+
+/// A program flying a ship from the other end of a socket. Before each update
+/// it is sent an observation of the game, as one line of JSON, and the game
+/// waits for it to answer with a line of JSON holding its controller state,
+/// for up to `ANSWER_TIMEOUT`. Once the game is over it is sent one last
+/// observation, marked as done.
+pub struct Bot {
+    stream: BufReader<TcpStream>,
+}
+
+impl Bot {
+    /// Waits for a bot to connect.
+    pub fn accept(listener: &TcpListener) -> io::Result<Self> {
+        let (stream, addr) = listener.accept()?;
+        log::info!("Bot connected from {}", addr);
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(ANSWER_TIMEOUT))?;
+        Ok(Self {
+            stream: BufReader::new(stream),
+        })
+    }
+
+    /// Shows the bot the game and waits for its move.
+    pub fn control(&mut self, observation: &Observation) -> io::Result<ControllerState> {
+        self.send(observation)?;
+
+        let mut line = String::new();
+        if self.stream.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(ErrorKind::UnexpectedEof, "the bot hung up"));
+        }
+        serde_json::from_str(&line).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Shows the bot how the game ended.
+    pub fn finish(&mut self, sim: &Simulation) -> io::Result<()> {
+        self.send(&Observation {
+            done: true,
+            ..Observation::of(sim)
+        })
+    }
+
+    fn send(&mut self, observation: &Observation) -> io::Result<()> {
+        let mut line = serde_json::to_vec(observation)?;
+        line.push(b'\n');
+        self.stream.get_mut().write_all(&line)
+    }
+}
+
+/// Plays a game without a window, under the bot's control, until the game is
+/// over or the bot leaves.
+pub fn play(bot: &mut Bot, mode: GameMode, seed: u64) -> Outcome {
+    let (sender, events) = mpsc::channel();
+    let mut sim = Simulation::new(
+        Bounds::new(0.0, 0.0, 800.0, 600.0),
+        Tuning::new(),
+        mode,
+        seed,
+        Arc::new(Mutex::new(sender)),
+    );
+
+    loop {
+        let controller = match bot.control(&Observation::of(&sim)) {
+            Ok(controller) => controller,
+            Err(e) => {
+                log::info!("Bot left: {}", e);
+                return Outcome {
+                    score: sim.score(),
+                    ticks: sim.replay.ticks(),
+                    is_over: false,
+                };
+            }
+        };
+        sim.step(TIMESTEP, &[controller]);
+
+        let is_over = events
+            .try_iter()
+            .any(|event| matches!(event, SceneEvent::GameOver { .. }));
+        if is_over {
+            if let Err(e) = bot.finish(&sim) {
+                log::info!("Bot left before the end: {}", e);
+            }
+            return Outcome {
+                score: sim.score(),
+                ticks: sim.replay.ticks(),
+                is_over,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, thread};
+
+    use super::*;
+
+    /// Plays a game of classic against a bot on the loopback interface, which
+    /// is handed the other end of the connection, and reports how it went.
+    fn against<T: Send + 'static>(
+        bot: impl FnOnce(BufReader<TcpStream>) -> T + Send + 'static,
+    ) -> (Outcome, T) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || bot(BufReader::new(TcpStream::connect(addr).unwrap())));
+
+        let mut server = Bot::accept(&listener).unwrap();
+        let outcome = play(&mut server, GameMode::Classic, 1);
+        drop(server);
+        (outcome, client.join().unwrap())
+    }
+
+    fn observe(stream: &mut BufReader<TcpStream>) -> Option<Observation> {
+        let mut line = String::new();
+        stream.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).ok()
+    }
+
+    fn answer(stream: &mut BufReader<TcpStream>) {
+        let mut line = serde_json::to_vec(&ControllerState::new()).unwrap();
+        line.push(b'\n');
+        stream.get_mut().write_all(&line).unwrap();
+    }
+
+    #[test]
+    fn answering_bots_play_until_the_game_is_over() {
+        let (outcome, last) = against(|mut stream| loop {
+            let observation = observe(&mut stream).unwrap();
+            if observation.done {
+                // Nothing more is sent once the game is over.
+                assert_eq!(stream.read(&mut [0]).unwrap(), 0);
+                return observation;
+            }
+            answer(&mut stream);
+        });
+
+        assert!(outcome.is_over);
+        assert_eq!(last.tick, outcome.ticks);
+        assert_eq!(last.score, outcome.score);
+    }
+
+    #[test]
+    fn silent_bots_are_taken_to_have_left() {
+        let (outcome, _) = against(|mut stream| {
+            for _ in 0..3 {
+                observe(&mut stream).unwrap();
+                answer(&mut stream);
+            }
+            observe(&mut stream).unwrap();
+            thread::sleep(ANSWER_TIMEOUT * 2);
+        });
+
+        assert!(!outcome.is_over);
+        assert_eq!(outcome.ticks, 3);
+    }
+
+    #[test]
+    fn bots_may_hang_up() {
+        let (outcome, _) = against(|mut stream| {
+            for _ in 0..3 {
+                observe(&mut stream).unwrap();
+                answer(&mut stream);
+            }
+        });
+
+        assert!(!outcome.is_over);
+        assert_eq!(outcome.ticks, 3);
+    }
+}
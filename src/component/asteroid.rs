@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Asteroid {
+    pub size: AsteroidSize,
+    /// Picks the asteroid's outline when it is drawn as line art.
+    #[serde(default)]
+    pub shape: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AsteroidSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl AsteroidSize {
+    pub fn radius(&self) -> f32 {
+        match self {
+            AsteroidSize::Small => 16.0,
+            AsteroidSize::Medium => 32.0,
+            AsteroidSize::Large => 48.0,
+        }
+    }
+
+    /// Number of bullets it takes to destroy an asteroid of this size.
+    pub fn hp(&self) -> f32 {
+        match self {
+            AsteroidSize::Small => 1.0,
+            AsteroidSize::Medium => 2.0,
+            AsteroidSize::Large => 4.0,
+        }
+    }
+
+    /// Smaller asteroids are harder to hit, so they are worth more.
+    pub fn value(&self) -> u32 {
+        match self {
+            AsteroidSize::Small => 3,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Large => 1,
+        }
+    }
+
+    /// The size of the fragments an asteroid breaks into when destroyed, if
+    /// any.
+    pub fn fragment(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Small => None,
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resource::clock::Instant;
+
+/// A powerful enemy which changes its behavior as it takes damage.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Boss {
+    pub kind: BossKind,
+    pub phase: u8,
+    /// When the boss next sheds debris.
+    pub next_shed: Instant,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BossKind {
+    /// A giant asteroid in an armored shell, which sheds rocks as it is broken
+    /// down.
+    ArmoredAsteroid,
+}
+
+/// Reduces damage taken by the given fraction.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Armor {
+    pub reduction: f32,
+}
+
+impl BossKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BossKind::ArmoredAsteroid => "Armored Asteroid",
+        }
+    }
+}
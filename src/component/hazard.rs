@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// An environmental hazard which affects everything within its radius.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HazardKind {
+    /// Pulls everything toward its center with an inverse-square force.
+    GravityWell { strength: f32 },
+    /// Pushes everything away from its center with an inverse-square force.
+    Repulsor { strength: f32 },
+    /// Slows everything passing through it, and hides whatever is inside.
+    Nebula { drag: f32 },
+}
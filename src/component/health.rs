@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resource::clock::Instant;
+
+/// Hit points of a destructible entity. An entity is destroyed once its hit
+/// points are exhausted.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Health {
+    pub hp: f32,
+    pub max: f32,
+    /// How long an entity is immune to further damage after taking a hit.
+    pub iframes: Duration,
+    pub invulnerable_until: Option<Instant>,
+}
+
+/// Damage dealt on contact to entities of an opposing faction.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Damage {
+    pub amount: f32,
+}
+
+/// Damage dealt continuously to an entity until the given time.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DamageOverTime {
+    pub per_second: f32,
+    pub until: Instant,
+}
+
+/// Entities only damage entities of a different faction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Faction {
+    /// The ship in the given seat, and whatever it fires. Players in different
+    /// seats can hurt each other.
+    Player(u8),
+    Hostile,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self {
+            hp: max,
+            max,
+            iframes: Duration::ZERO,
+            invulnerable_until: None,
+        }
+    }
+
+    pub fn with_iframes(self, iframes: Duration) -> Self {
+        Self { iframes, ..self }
+    }
+
+    pub fn is_invulnerable(&self, now: Instant) -> bool {
+        matches!(self.invulnerable_until, Some(until) if now < until)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hp <= 0.0
+    }
+
+    /// Deals a discrete hit of damage, after which the entity is briefly
+    /// invulnerable. Returns true if the damage was dealt.
+    pub fn hit(&mut self, amount: f32, now: Instant) -> bool {
+        if !self.drain(amount, now) {
+            return false;
+        }
+
+        if self.iframes > Duration::ZERO {
+            self.invulnerable_until = Some(now + self.iframes);
+        }
+        true
+    }
+
+    /// Deals continuous damage, which does not grant invulnerability. Returns
+    /// true if the damage was dealt.
+    pub fn drain(&mut self, amount: f32, now: Instant) -> bool {
+        if self.is_invulnerable(now) || self.is_dead() {
+            return false;
+        }
+
+        self.hp -= amount;
+        true
+    }
+}
@@ -1,19 +1,75 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
 
+use crate::resource::clock::Instant;
+
+/// Hands every component which makes up a game's world to the given macro,
+/// as `Type => "name"` pairs, the name being what the component is saved
+/// under. Whatever must deal with every component, such as copying or saving
+/// a world, works from this one list, so that none can be left out.
+macro_rules! components {
+    ($callback:ident) => {
+        $callback! {
+            $crate::component::Armor => "armor",
+            $crate::component::Asteroid => "asteroid",
+            $crate::component::Body => "body",
+            $crate::component::Boss => "boss",
+            $crate::component::Bullet => "bullet",
+            $crate::component::Damage => "damage",
+            $crate::component::DamageOverTime => "damage_over_time",
+            $crate::component::Emitter => "emitter",
+            $crate::component::Faction => "faction",
+            $crate::component::GameOverTimeout => "game_over_timeout",
+            $crate::component::Grazing => "grazing",
+            $crate::component::Hazard => "hazard",
+            $crate::component::Health => "health",
+            $crate::component::HitMask => "hit_mask",
+            $crate::component::Missile => "missile",
+            $crate::component::Particle => "particle",
+            $crate::component::Player => "player",
+            $crate::component::PlayerInput => "player_input",
+            $crate::component::ScorePopup => "score_popup",
+            $crate::component::ScoreValue => "score_value",
+            $crate::component::SpawnTimeout => "spawn_timeout",
+            $crate::component::Spatial => "spatial",
+            $crate::component::Sprite => "sprite",
+            $crate::component::Weapon => "weapon",
+        }
+    };
+}
+
+mod asteroid;
+mod boss;
+mod hazard;
+mod health;
+mod particle;
 mod spatial;
 mod sprite;
+mod weapon;
 
+pub use asteroid::{Asteroid, AsteroidSize};
+pub use boss::{Armor, Boss, BossKind};
+pub use hazard::{Hazard, HazardKind};
+pub use health::{Damage, DamageOverTime, Faction, Health};
+pub use particle::{Emitter, Particle};
 pub use spatial::Spatial;
 pub use sprite::Sprite;
 pub use sprite::SpriteKind;
+pub use weapon::{Missile, ProjectileKind, Weapon, WeaponKind, LASER_RANGE};
 
-pub struct Asteroid;
-
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Bullet;
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Player;
 
-#[derive(Copy, Clone)]
+/// A physical body which collides elastically with other bodies. Only bodies
+/// with a `HitMask::Circle` participate in collisions.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Body {
+    pub mass: f32,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum WrapAround {
     /// An entity should wrap around the edge of the screen
     Wrap,
@@ -21,15 +77,49 @@ pub enum WrapAround {
     Destroy,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SpawnTimeout {
     pub when: Instant,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Ends the game at the given time, giving the player a moment to watch their
+/// ship explode.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct GameOverTimeout {
+    pub when: Instant,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum HitMask {
     Circle { radius: f32 },
     Point,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct PlayerInput;
+/// A ship flown by the player in the given seat.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub seat: u8,
+    /// Whether the weapon switch was held last update, so that holding it only
+    /// switches weapons once.
+    pub was_switching: bool,
+}
+
+/// Base points awarded for destroying an entity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScoreValue(pub u32);
+
+/// Floating text showing points awarded at the location they were earned.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ScorePopup {
+    pub points: u32,
+    pub multiplier: u32,
+    pub until: Instant,
+}
+
+/// Marks an asteroid which is passing dangerously close to the player. If the
+/// player has not lost hull points by the time the asteroid passes, the player
+/// is awarded a near-miss bonus.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Grazing {
+    pub player_hp: f32,
+}
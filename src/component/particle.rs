@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resource::clock::Instant;
+
+/// A short-lived, purely cosmetic speck which fades and shrinks (or grows)
+/// over its lifetime.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Particle {
+    pub born: Instant,
+    pub lifetime: Duration,
+    /// Color at birth and at death.
+    pub color: ([f32; 4], [f32; 4]),
+    /// Width in pixels at birth and at death.
+    pub size: (f32, f32),
+}
+
+/// Spawns particles at the position of its entity. Particles inherit the
+/// velocity of the emitting entity.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Emitter {
+    pub active: bool,
+    /// Particles emitted per second while active.
+    pub rate: f32,
+    /// If set, the emitter releases this many particles at once and is then
+    /// removed from play.
+    pub burst: Option<u32>,
+    /// Direction particles are emitted in, in radians.
+    pub direction: f32,
+    /// How far, in radians, particles may deviate from `direction`.
+    pub spread: f32,
+    /// Minimum and maximum speed of emitted particles, in pixels per second.
+    pub speed: (f32, f32),
+    pub lifetime: (Duration, Duration),
+    pub color: ([f32; 4], [f32; 4]),
+    pub size: (f32, f32),
+    /// Fractional particles carried over between updates.
+    pub pending: f32,
+}
+
+impl Particle {
+    /// How far through its lifetime this particle is, from 0 to 1.
+    pub fn age(&self, now: Instant) -> f32 {
+        let age = now.saturating_duration_since(self.born).as_secs_f32();
+        (age / self.lifetime.as_secs_f32()).min(1.0)
+    }
+
+    pub fn color_at(&self, now: Instant) -> [f32; 4] {
+        let t = self.age(now);
+        let (from, to) = self.color;
+        [
+            from[0] + (to[0] - from[0]) * t,
+            from[1] + (to[1] - from[1]) * t,
+            from[2] + (to[2] - from[2]) * t,
+            from[3] + (to[3] - from[3]) * t,
+        ]
+    }
+
+    pub fn size_at(&self, now: Instant) -> f32 {
+        let t = self.age(now);
+        self.size.0 + (self.size.1 - self.size.0) * t
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now >= self.born + self.lifetime
+    }
+}
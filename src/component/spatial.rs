@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::WrapAround;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Spatial {
     pub x: f32,
     pub y: f32,
@@ -8,5 +10,7 @@ pub struct Spatial {
     pub dy: f32,
     /// angle of orientation ("where it's pointing"), in radians
     pub angle_o: f32,
+    /// angular velocity ("how fast it's turning"), in radians per second
+    pub spin: f32,
     pub wrap: WrapAround,
 }
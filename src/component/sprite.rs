@@ -1,13 +1,16 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Sprite {
     pub width: u32,
     pub height: u32,
     pub kind: SpriteKind,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum SpriteKind {
     Asteroid,
+    Boss,
     Bullet,
     Player,
     Title,
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resource::clock::Instant;
+
+/// A gun mounted on a ship or enemy. Whoever carries it pulls the trigger, and
+/// the weapon system fires projectiles on behalf of the carrier's faction.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub projectile: Projectile,
+    /// Delay after a trigger pull before the weapon may fire again.
+    pub cooldown: Duration,
+    /// Distance ahead of the carrier at which projectiles appear.
+    pub muzzle: f32,
+    /// Projectiles fired side by side in each volley.
+    pub volley: u32,
+    /// Angle between adjacent projectiles in a volley, in radians.
+    pub spread: f32,
+    /// Sideways distance between adjacent projectiles in a volley.
+    pub gap: f32,
+    /// Volleys fired per trigger pull.
+    pub burst: u32,
+    pub burst_interval: Duration,
+    /// Heat gained per volley, or per second of firing for a laser. The weapon
+    /// overheats at a heat of 1.0 and may not fire again until it has cooled
+    /// off entirely.
+    pub heat_per_volley: f32,
+    /// Heat lost per second.
+    pub cooling: f32,
+
+    pub trigger: bool,
+    pub heat: f32,
+    pub overheated: bool,
+    pub ready_at: Option<Instant>,
+    /// Volleys remaining in the current burst.
+    pub pending: u32,
+    /// When a charge weapon began charging.
+    pub charging_since: Option<Instant>,
+    /// Length of a laser's beam while it is firing.
+    pub beam: Option<f32>,
+}
+
+/// The built-in weapons.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum WeaponKind {
+    Single,
+    Twin,
+    Spread,
+    Burst,
+    /// Hold the trigger to charge up, release to fire a single, more powerful
+    /// shot.
+    Charge,
+    Missile,
+    /// A continuous beam which damages the first thing in its path for as
+    /// long as the trigger is held.
+    Laser,
+}
+
+/// What a weapon fires.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Projectile {
+    pub kind: ProjectileKind,
+    pub speed: f32,
+    /// Damage dealt on contact, or per second for a laser.
+    pub damage: f32,
+    /// Projectiles without a radius only hit things larger than a point.
+    pub radius: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileKind {
+    Bullet,
+    Missile,
+}
+
+/// A projectile which steers itself toward the nearest target until it runs
+/// out of fuel.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Missile {
+    pub speed: f32,
+    /// How quickly the missile can turn, in radians per second.
+    pub turn_rate: f32,
+    pub fuel_until: Instant,
+}
+
+/// How long a charge weapon takes to reach full power.
+const FULL_CHARGE: Duration = Duration::from_secs(1);
+/// How much more damage a fully charged shot deals.
+const CHARGE_MULTIPLIER: f32 = 4.0;
+
+/// How far a laser beam reaches.
+pub const LASER_RANGE: f32 = 600.0;
+
+const BULLET: Projectile = Projectile {
+    kind: ProjectileKind::Bullet,
+    speed: 1_000.0,
+    damage: 1.0,
+    radius: None,
+};
+
+impl Weapon {
+    pub fn new(kind: WeaponKind) -> Self {
+        let weapon = Self {
+            kind,
+            projectile: BULLET,
+            cooldown: Duration::from_millis(333),
+            muzzle: 16.0,
+            volley: 1,
+            spread: 0.0,
+            gap: 0.0,
+            burst: 1,
+            burst_interval: Duration::ZERO,
+            heat_per_volley: 0.0,
+            cooling: 0.5,
+            trigger: false,
+            heat: 0.0,
+            overheated: false,
+            ready_at: None,
+            pending: 0,
+            charging_since: None,
+            beam: None,
+        };
+
+        match kind {
+            WeaponKind::Single => weapon,
+            WeaponKind::Twin => Self {
+                volley: 2,
+                gap: 12.0,
+                cooldown: Duration::from_millis(250),
+                heat_per_volley: 0.08,
+                ..weapon
+            },
+            WeaponKind::Spread => Self {
+                volley: 5,
+                spread: 0.15,
+                cooldown: Duration::from_millis(500),
+                heat_per_volley: 0.15,
+                ..weapon
+            },
+            WeaponKind::Burst => Self {
+                burst: 3,
+                burst_interval: Duration::from_millis(60),
+                cooldown: Duration::from_millis(400),
+                heat_per_volley: 0.05,
+                ..weapon
+            },
+            WeaponKind::Charge => Self {
+                projectile: Projectile {
+                    speed: 800.0,
+                    ..BULLET
+                },
+                cooldown: Duration::from_millis(200),
+                heat_per_volley: 0.3,
+                ..weapon
+            },
+            WeaponKind::Missile => Self {
+                projectile: Projectile {
+                    kind: ProjectileKind::Missile,
+                    speed: 450.0,
+                    damage: 2.0,
+                    radius: None,
+                },
+                volley: 2,
+                spread: 0.8,
+                cooldown: Duration::from_millis(700),
+                heat_per_volley: 0.2,
+                ..weapon
+            },
+            WeaponKind::Laser => Self {
+                projectile: Projectile {
+                    damage: 6.0,
+                    ..BULLET
+                },
+                heat_per_volley: 0.35,
+                cooling: 0.3,
+                ..weapon
+            },
+        }
+    }
+
+    /// How far along a charge weapon's charge is, from 0.0 to 1.0.
+    pub fn charge(&self, now: Instant) -> f32 {
+        self.charging_since.map_or(0.0, |since| {
+            ((now - since).as_secs_f32() / FULL_CHARGE.as_secs_f32()).min(1.0)
+        })
+    }
+
+    /// Damage multiplier of a charge shot released now.
+    pub fn power(&self, now: Instant) -> f32 {
+        1.0 + self.charge(now) * (CHARGE_MULTIPLIER - 1.0)
+    }
+
+    pub fn is_ready(&self, now: Instant) -> bool {
+        !self.overheated && self.ready_at.is_none_or(|at| now >= at)
+    }
+}
+
+impl WeaponKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeaponKind::Single => "Single",
+            WeaponKind::Twin => "Twin",
+            WeaponKind::Spread => "Spread",
+            WeaponKind::Burst => "Burst",
+            WeaponKind::Charge => "Charge",
+            WeaponKind::Missile => "Missile",
+            WeaponKind::Laser => "Laser",
+        }
+    }
+
+    /// The next weapon in the player's rotation.
+    pub fn next(&self) -> Self {
+        match self {
+            WeaponKind::Single => WeaponKind::Twin,
+            WeaponKind::Twin => WeaponKind::Spread,
+            WeaponKind::Spread => WeaponKind::Burst,
+            WeaponKind::Burst => WeaponKind::Charge,
+            WeaponKind::Charge => WeaponKind::Missile,
+            WeaponKind::Missile => WeaponKind::Laser,
+            WeaponKind::Laser => WeaponKind::Single,
+        }
+    }
+}
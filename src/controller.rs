@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 macro_rules! button_press {
-    ($button:ident) => {
+    ($button:pat) => {
         piston::Event::Input(
             piston::Input::Button(piston::ButtonArgs {
                 state: piston::ButtonState::Press,
@@ -15,7 +17,7 @@ macro_rules! button_press {
 }
 
 macro_rules! button_release {
-    ($button:ident) => {
+    ($button:pat) => {
         piston::Event::Input(
             piston::Input::Button(piston::ButtonArgs {
                 state: piston::ButtonState::Release,
@@ -43,19 +45,25 @@ macro_rules! axis {
     };
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ControllerState {
     pub left_thumb: (f64, f64),
     pub right_thumb: (f64, f64),
-    pub right_bumper: bool,
+    pub switch_weapon: bool,
+    pub fire: bool,
 }
 
+/// The controller state of every player in the game, indexed by seat.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Controllers(pub Vec<ControllerState>);
+
 impl ControllerState {
     pub fn new() -> Self {
         ControllerState {
             left_thumb: (0.0, 0.0),
             right_thumb: (0.0, 0.0),
-            right_bumper: false,
+            switch_weapon: false,
+            fire: false,
         }
     }
 
@@ -69,11 +77,25 @@ impl ControllerState {
         }
     }
 
-    pub fn press_right_bumper(&mut self) {
-        self.right_bumper = true
+    pub fn press_switch_weapon(&mut self) {
+        self.switch_weapon = true
     }
 
-    pub fn release_right_bumper(&mut self) {
-        self.right_bumper = false
+    pub fn release_switch_weapon(&mut self) {
+        self.switch_weapon = false
+    }
+
+    pub fn press_fire(&mut self) {
+        self.fire = true
+    }
+
+    pub fn release_fire(&mut self) {
+        self.fire = false
+    }
+}
+
+impl Default for ControllerState {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -0,0 +1,99 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{resource::score::Score, storage};
+
+/// Daily challenge history kept before there were player profiles. Now each
+/// profile keeps its own.
+const LEGACY_FILE: &str = "daily.json";
+
+/// A calendar date in UTC, so that everyone plays the same challenge on the
+/// same day regardless of time zone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// A player's attempt at a daily challenge.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Attempt {
+    pub date: Date,
+    pub score: Score,
+}
+
+/// Every daily challenge the player has attempted, oldest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_days((seconds / 86_400) as i64)
+    }
+
+    /// Converts days since 1970-01-01 to a date in the proleptic Gregorian
+    /// calendar. See http://howardhinnant.github.io/date_algorithms.html.
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self { year, month, day }
+    }
+
+    /// The seed of this date's challenge.
+    pub fn seed(&self) -> u64 {
+        (self.year as u64) * 10_000 + (self.month as u64) * 100 + self.day as u64
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl History {
+    /// Loads the history kept before there were player profiles.
+    pub fn load_legacy() -> Self {
+        storage::load_or_default(LEGACY_FILE)
+    }
+
+    pub fn attempt(&self, date: Date) -> Option<&Attempt> {
+        self.attempts.iter().find(|attempt| attempt.date == date)
+    }
+
+    /// Records the start of an attempt. The attempt counts even if it is
+    /// abandoned, so each day's challenge may only be attempted once.
+    pub fn begin(&mut self, date: Date) {
+        if self.attempt(date).is_none() {
+            self.attempts.push(Attempt {
+                date,
+                score: Score::new(),
+            });
+        }
+    }
+
+    /// Records the final score of the given day's attempt.
+    pub fn finish(&mut self, date: Date, score: Score) {
+        if let Some(attempt) = self.attempts.iter_mut().find(|a| a.date == date) {
+            attempt.score = score;
+        }
+    }
+}
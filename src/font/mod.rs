@@ -1,15 +1,23 @@
 use std::error::Error;
 
 use freetype::{face::LoadFlag, Face};
-use graphics::{Context, Image, Transformed};
-use opengl_graphics::{GlGraphics, Texture, TextureSettings};
+use graphics::Context;
+use image::{Rgba, RgbaImage};
+use opengl_graphics::GlGraphics;
+
+use crate::atlas::{Atlas, Packer, SpriteBatch};
+
+/// Size of the pages glyphs are packed onto, which fits a whole font of the
+/// size the game uses.
+const PAGE_SIZE: u32 = 512;
 
 pub struct Font {
     glyphs: Vec<Glyph>,
+    /// Every glyph's bitmap, keyed by its character code.
+    atlas: Atlas<usize>,
 }
 
 pub struct Glyph {
-    pub texture: Texture,
     pub size: Size,
     pub bearing: Bearing,
     pub advance: f32,
@@ -39,22 +47,22 @@ pub enum Layout {
 impl Font {
     pub fn new(face: &Face) -> Result<Self, Box<dyn Error>> {
         let mut glyphs = Vec::new();
+        let mut packer = Packer::new(PAGE_SIZE);
         for char_code in 0..127 {
             face.load_char(char_code, LoadFlag::RENDER)?;
             let glyph = face.glyph();
             let bitmap = glyph.bitmap();
             let vector = glyph.advance();
 
-            let texture = Texture::from_memory_alpha(
-                bitmap.buffer(),
-                bitmap.width() as u32,
-                bitmap.rows() as u32,
-                &TextureSettings::new(),
-            )
-            .unwrap();
+            // Glyphs are white, and as opaque as they are inked.
+            let buffer = bitmap.buffer();
+            let pitch = bitmap.pitch() as usize;
+            let image = RgbaImage::from_fn(bitmap.width() as u32, bitmap.rows() as u32, |x, y| {
+                Rgba([255, 255, 255, buffer[y as usize * pitch + x as usize]])
+            });
+            packer.add(char_code, image);
 
             let character = Glyph {
-                texture,
                 size: Size {
                     width: bitmap.width() as u32,
                     height: bitmap.rows() as u32,
@@ -69,7 +77,10 @@ impl Font {
             glyphs.push(character);
         }
 
-        Ok(Self { glyphs })
+        Ok(Self {
+            glyphs,
+            atlas: Atlas::new(packer)?,
+        })
     }
 
     pub fn create_text(&self, text: &str) -> Result<Text, Box<dyn Error>> {
@@ -106,6 +117,7 @@ impl Font {
         let width: f64 = lines.iter().map(|x| x.width).fold(0.0, |a, b| a.max(b));
         let height: f64 = lines.iter().map(|x| x.height).intersperse(TOP_PAD).sum();
 
+        let mut batch = SpriteBatch::new(&self.atlas);
         let mut top = center.1 - height / 2.0;
         for line in lines {
             let left = match &layout {
@@ -113,10 +125,11 @@ impl Font {
                 Layout::LeftAlign => center.0 - width / 2.0,
             };
 
-            self.render_line(&line, (left, top), color, &c, g)?;
+            self.render_line(line, (left, top), color, c, &mut batch)?;
 
             top += line.height + TOP_PAD;
         }
+        batch.draw(&c.draw_state, g);
 
         Ok(())
     }
@@ -127,7 +140,7 @@ impl Font {
         origin: (f64, f64),
         color: [f32; 4],
         c: &Context,
-        gl: &mut GlGraphics,
+        batch: &mut SpriteBatch<usize>,
     ) -> Result<(), Box<dyn Error>> {
         let mut x = origin.0;
         let y = origin.1;
@@ -136,11 +149,16 @@ impl Font {
             // let offset_y = glyph.size.height as f64 - glyph.bearing.top as f64;
             let offset_y = -glyph.bearing.top as f64;
             let offset_x = glyph.bearing.left as f64;
-            Image::new_color(color).draw(
-                &glyph.texture,
-                &c.draw_state,
-                c.transform.trans(x + offset_x, y + offset_y as f64),
-                gl,
+            batch.push(
+                &(char as usize),
+                [
+                    x + offset_x,
+                    y + offset_y,
+                    glyph.size.width as f64,
+                    glyph.size.height as f64,
+                ],
+                color,
+                c.transform,
             );
             x += glyph.advance as f64;
         }
@@ -0,0 +1,137 @@
+use legion::IntoQuery;
+
+use crate::{
+    component::{Bullet, Faction, HitMask, Spatial},
+    resource::bounds::Bounds,
+    scene::Simulation,
+};
+
+/// Shades of grey each kind of entity is drawn in.
+const THREAT: u8 = 255;
+const SHIP: u8 = 160;
+const BULLET: u8 = 96;
+
+/// A picture of the whole arena, one byte of brightness per pixel, row by
+/// row. Entities are drawn as the shapes they collide as, rather than their
+/// sprites, so that the picture shows exactly what can be hit.
+#[derive(Clone)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Frame {
+    /// Draws the game at the given size.
+    pub fn draw(sim: &Simulation, width: usize, height: usize) -> Self {
+        let arena = sim.resources.get::<Bounds>().unwrap().inner;
+        let (sx, sy) = (width as f32 / arena.width, height as f32 / arena.height);
+        let mut frame = Self {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        };
+
+        <(&Spatial, &HitMask, &Faction, Option<&Bullet>)>::query().for_each(
+            &sim.world,
+            |(spatial, mask, faction, bullet)| {
+                let shade = match (faction, bullet) {
+                    (_, Some(_)) => BULLET,
+                    (Faction::Hostile, None) => THREAT,
+                    (Faction::Player(_), None) => SHIP,
+                };
+                let (x, y) = ((spatial.x - arena.p0.x) * sx, (spatial.y - arena.p0.y) * sy);
+                match mask {
+                    // Anything smaller than a pixel still covers one.
+                    HitMask::Circle { radius } => frame.fill_ellipse(
+                        x,
+                        y,
+                        (radius * sx).max(0.5),
+                        (radius * sy).max(0.5),
+                        shade,
+                    ),
+                    HitMask::Point => frame.plot(x as isize, y as isize, shade),
+                }
+            },
+        );
+
+        frame
+    }
+
+    fn fill_ellipse(&mut self, x: f32, y: f32, rx: f32, ry: f32, shade: u8) {
+        let top = (y - ry).floor() as isize;
+        let bottom = (y + ry).ceil() as isize;
+        let left = (x - rx).floor() as isize;
+        let right = (x + rx).ceil() as isize;
+        for row in top..=bottom {
+            for column in left..=right {
+                // Pixels are covered if their centers are.
+                let dx = (column as f32 + 0.5 - x) / rx;
+                let dy = (row as f32 + 0.5 - y) / ry;
+                if dx * dx + dy * dy <= 1.0 {
+                    self.plot(column, row, shade);
+                }
+            }
+        }
+    }
+
+    fn plot(&mut self, column: isize, row: isize, shade: u8) {
+        if (0..self.width as isize).contains(&column) && (0..self.height as isize).contains(&row) {
+            let pixel = &mut self.pixels[row as usize * self.width + column as usize];
+            *pixel = (*pixel).max(shade);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc, Mutex};
+
+    use crate::{
+        component::WrapAround,
+        resource::{game_mode::GameMode, tuning::Tuning},
+    };
+
+    use super::*;
+
+    fn threat(x: f32, y: f32, radius: f32) -> (Spatial, HitMask, Faction) {
+        let spatial = Spatial {
+            x,
+            y,
+            dx: 0.0,
+            dy: 0.0,
+            angle_o: 0.0,
+            spin: 0.0,
+            wrap: WrapAround::Wrap,
+        };
+        (spatial, HitMask::Circle { radius }, Faction::Hostile)
+    }
+
+    #[test]
+    fn entities_off_the_edge_are_clipped() {
+        let (sender, _events) = mpsc::channel();
+        let mut sim = Simulation::new(
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+            Tuning::new(),
+            GameMode::Zen,
+            1,
+            Arc::new(Mutex::new(sender)),
+        );
+        let arena = sim.resources.get::<Bounds>().unwrap().inner;
+        let (right, bottom) = (arena.p0.x + arena.width, arena.p0.y + arena.height);
+        sim.world.extend(vec![
+            // Straddling each corner.
+            threat(arena.p0.x, arena.p0.y, 100.0),
+            threat(right, bottom, 100.0),
+            // Far outside, and larger than the whole arena.
+            threat(-10_000.0, -10_000.0, 10.0),
+            threat(right / 2.0, bottom / 2.0, 10_000.0),
+        ]);
+
+        for &(width, height) in &[(0, 0), (1, 1), (7, 3), (80, 60)] {
+            let frame = Frame::draw(&sim, width, height);
+            assert_eq!(frame.pixels.len(), width * height);
+            assert!(frame.pixels.iter().all(|&pixel| pixel == THREAT));
+        }
+    }
+}
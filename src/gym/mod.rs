@@ -0,0 +1,346 @@
+mod frame;
+mod sensor;
+
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    bot::{self, Observation as Entities},
+    controller::ControllerState,
+    resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+    scene::{SceneEvent, Simulation},
+};
+
+pub use frame::Frame;
+
+/// How an environment describes the game to an agent.
+#[derive(Clone, Copy, Debug)]
+pub enum Encoding {
+    /// Every ship and asteroid, as bots see them.
+    Entities,
+    /// The distance to the nearest threat along rays cast evenly around the
+    /// ship, starting from its nose.
+    Rays { count: usize, range: f32 },
+    /// The arena drawn in shades of grey, shrunk to the given size.
+    Frame { width: usize, height: usize },
+}
+
+#[derive(Clone)]
+pub enum Observation {
+    Entities(Entities),
+    /// Each ray's distance to the nearest threat as a fraction of its range, or
+    /// 1.0 if there is none within range.
+    Rays(Vec<f32>),
+    Frame(Frame),
+}
+
+/// What an agent sees after an action, the points it scored, and whether the
+/// game is over.
+pub type Step = (Observation, f32, bool);
+
+/// The game an environment plays, and how it is shown to the agent.
+#[derive(Clone, Copy)]
+pub struct Config {
+    pub mode: GameMode,
+    pub tuning: Tuning,
+    /// Size of the window the game is played in, as if it had one.
+    pub bounds: Bounds,
+    pub encoding: Encoding,
+    /// Updates played with each action.
+    pub frame_skip: u32,
+    /// Updates after which a game is cut short, if any.
+    pub max_ticks: Option<u64>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self {
+            mode: GameMode::Classic,
+            tuning: Tuning::new(),
+            bounds: Bounds::new(0.0, 0.0, 800.0, 600.0),
+            encoding: Encoding::Entities,
+            frame_skip: 4,
+            max_ticks: None,
+        }
+    }
+
+    /// Whether environments can be made with this configuration.
+    pub fn check(&self) -> Result<(), ConfigError> {
+        match self.encoding {
+            Encoding::Rays { range, .. } if !(range.is_finite() && range > 0.0) => {
+                Err(ConfigError::RayRange(range))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why an environment can't be made with a configuration.
+#[derive(Clone, Copy, Debug)]
+pub enum ConfigError {
+    /// Rays must reach some way out from the ship, but not forever.
+    RayRange(f32),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::RayRange(range) => {
+                write!(f, "rays can't be cast with a range of {}", range)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// The game as a reinforcement-learning environment, in the manner of Gym:
+/// the agent picks a controller state, and is rewarded with the points it
+/// scores.
+pub struct Env {
+    config: Config,
+    sim: Simulation,
+    events: Receiver<SceneEvent>,
+    is_done: bool,
+}
+
+impl Env {
+    /// Makes an environment, ready to play a game with seed zero.
+    pub fn new(config: Config) -> Result<Self, ConfigError> {
+        config.check()?;
+        let (sim, events) = start(&config, 0);
+        Ok(Self {
+            config,
+            sim,
+            events,
+            is_done: false,
+        })
+    }
+
+    /// Starts a new game.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        let (sim, events) = start(&self.config, seed);
+        self.sim = sim;
+        self.events = events;
+        self.is_done = false;
+        self.observe()
+    }
+
+    /// Plays the action for the configured number of updates. Returns what
+    /// the agent sees afterwards, the points scored meanwhile, and whether the
+    /// game is over. Once it is, further steps do nothing until the next
+    /// reset.
+    pub fn step(&mut self, action: ControllerState) -> Step {
+        let before = self.sim.score().points();
+        for _ in 0..self.config.frame_skip.max(1) {
+            if self.is_done {
+                break;
+            }
+            self.sim.step(bot::TIMESTEP, &[action]);
+
+            let is_over = self
+                .events
+                .try_iter()
+                .any(|event| matches!(event, SceneEvent::GameOver { .. }));
+            let is_cut_short = self
+                .config
+                .max_ticks
+                .is_some_and(|max| self.sim.replay.ticks() >= max);
+            self.is_done = is_over || is_cut_short;
+        }
+
+        let reward = (self.sim.score().points() - before) as f32;
+        (self.observe(), reward, self.is_done)
+    }
+
+    pub fn observe(&self) -> Observation {
+        match self.config.encoding {
+            Encoding::Entities => Observation::Entities(Entities::of(&self.sim)),
+            Encoding::Rays { count, range } => {
+                Observation::Rays(sensor::cast(&self.sim, count, range))
+            }
+            Encoding::Frame { width, height } => {
+                Observation::Frame(Frame::draw(&self.sim, width, height))
+            }
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.sim
+    }
+}
+
+fn start(config: &Config, seed: u64) -> (Simulation, Receiver<SceneEvent>) {
+    let (sender, events) = mpsc::channel();
+    let sim = Simulation::new(
+        config.bounds,
+        config.tuning,
+        config.mode,
+        seed,
+        Arc::new(Mutex::new(sender)),
+    );
+    (sim, events)
+}
+
+enum Command {
+    Reset(u64),
+    Step(ControllerState),
+}
+
+/// Many environments stepped together, each on its own thread.
+///
+/// Environments whose game ends are started over straight away, with the seed
+/// of their last game plus the number of environments, so that every game
+/// played has its own seed. The observation returned with the end of a game is
+/// therefore the start of the next one.
+pub struct VecEnv {
+    workers: Vec<(Sender<Command>, Receiver<Step>)>,
+}
+
+impl VecEnv {
+    pub fn new(count: usize, config: Config) -> Result<Self, ConfigError> {
+        config.check()?;
+        let workers = (0..count)
+            .map(|_| {
+                let (commands, command_receiver) = mpsc::channel();
+                let (result_sender, results) = mpsc::channel();
+                // Simulations can't move between threads, so each is made on
+                // the thread which plays it.
+                thread::spawn(move || {
+                    let mut env = Env::new(config).unwrap();
+                    let mut seed = 0;
+                    for command in command_receiver {
+                        let result = match command {
+                            Command::Reset(new_seed) => {
+                                seed = new_seed;
+                                (env.reset(seed), 0.0, false)
+                            }
+                            Command::Step(action) => match env.step(action) {
+                                (_, reward, true) => {
+                                    seed = seed.wrapping_add(count as u64);
+                                    (env.reset(seed), reward, true)
+                                }
+                                result => result,
+                            },
+                        };
+                        if result_sender.send(result).is_err() {
+                            return;
+                        }
+                    }
+                });
+                (commands, results)
+            })
+            .collect();
+
+        Ok(Self { workers })
+    }
+
+    /// Starts every environment over, the first with the given seed and each
+    /// of the rest with the next.
+    pub fn reset(&mut self, seed: u64) -> Vec<Observation> {
+        let commands = (0..self.workers.len() as u64)
+            .map(|i| Command::Reset(seed.wrapping_add(i)))
+            .collect();
+        self.run(commands)
+            .into_iter()
+            .map(|(observation, _, _)| observation)
+            .collect()
+    }
+
+    /// Steps each environment with its own action.
+    pub fn step(&mut self, actions: &[ControllerState]) -> Vec<Step> {
+        assert_eq!(actions.len(), self.workers.len());
+        self.run(actions.iter().copied().map(Command::Step).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    fn run(&self, commands: Vec<Command>) -> Vec<Step> {
+        // Every environment is set going before any is waited on.
+        for ((sender, _), command) in self.workers.iter().zip(commands) {
+            sender.send(command).unwrap();
+        }
+        self.workers
+            .iter()
+            .map(|(_, results)| results.recv().unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Games cut short after two steps.
+    fn config() -> Config {
+        Config {
+            frame_skip: 4,
+            max_ticks: Some(8),
+            ..Config::new()
+        }
+    }
+
+    /// The entities seen, in a form which can be compared.
+    fn seen(observation: &Observation) -> String {
+        match observation {
+            Observation::Entities(entities) => serde_json::to_string(entities).unwrap(),
+            _ => panic!("expected entities"),
+        }
+    }
+
+    fn start_of(seed: u64) -> String {
+        seen(&Env::new(config()).unwrap().reset(seed))
+    }
+
+    #[test]
+    fn environments_start_over_with_the_next_unplayed_seed() {
+        let mut envs = VecEnv::new(2, config()).unwrap();
+        let observations = envs.reset(10);
+        assert_eq!(seen(&observations[0]), start_of(10));
+        assert_eq!(seen(&observations[1]), start_of(11));
+
+        let idle = [ControllerState::new(); 2];
+        for next in &[[12, 13], [14, 15]] {
+            for (_, _, done) in envs.step(&idle) {
+                assert!(!done);
+            }
+            let steps = envs.step(&idle);
+            for ((observation, _, done), &seed) in steps.iter().zip(next) {
+                assert!(*done);
+                assert_eq!(seen(observation), start_of(seed));
+            }
+        }
+    }
+
+    #[test]
+    fn rays_must_have_a_range() {
+        for &range in &[0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let config = Config {
+                encoding: Encoding::Rays { count: 8, range },
+                ..Config::new()
+            };
+            assert!(matches!(config.check(), Err(ConfigError::RayRange(_))));
+            assert!(Env::new(config).is_err());
+            assert!(VecEnv::new(2, config).is_err());
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::f32::consts::TAU;
+
+use legion::IntoQuery;
+
+use crate::{
+    component::{Faction, HitMask, PlayerInput, Spatial},
+    scene::Simulation,
+};
+
+/// Casts rays evenly around the first player's ship, starting from its nose,
+/// and measures how far each travels before meeting a threat, as a fraction of
+/// the range.
+pub fn cast(sim: &Simulation, count: usize, range: f32) -> Vec<f32> {
+    let ship = <(&Spatial, &PlayerInput)>::query()
+        .iter(&sim.world)
+        .find(|(_, input)| input.seat == 0)
+        .map(|(spatial, _)| *spatial);
+    let ship = match ship {
+        Some(ship) => ship,
+        None => return vec![1.0; count],
+    };
+
+    let threats: Vec<_> = <(&Spatial, &HitMask, &Faction)>::query()
+        .iter(&sim.world)
+        .filter_map(|(spatial, mask, faction)| match (mask, faction) {
+            (HitMask::Circle { radius }, Faction::Hostile) => Some((spatial.x, spatial.y, *radius)),
+            _ => None,
+        })
+        .collect();
+
+    (0..count)
+        .map(|i| {
+            let angle = ship.angle_o + i as f32 * TAU / count as f32;
+            let (dy, dx) = angle.sin_cos();
+            threats
+                .iter()
+                .filter_map(|&(x, y, radius)| distance(ship.x, ship.y, dx, dy, x, y, radius))
+                .fold(range, f32::min)
+                / range
+        })
+        .collect()
+}
+
+/// How far a ray travels before entering a circle, if it ever does. A ray
+/// starting inside the circle is already there.
+fn distance(x0: f32, y0: f32, dx: f32, dy: f32, x: f32, y: f32, radius: f32) -> Option<f32> {
+    let (mx, my) = (x - x0, y - y0);
+    // Distance along the ray to the point nearest the center, and the square
+    // of that point's distance from it.
+    let along = mx * dx + my * dy;
+    let miss = mx * mx + my * my - along * along;
+    if miss > radius * radius {
+        return None;
+    }
+
+    let half_chord = (radius * radius - miss).sqrt();
+    match (along - half_chord, along + half_chord) {
+        (_, exit) if exit < 0.0 => None,
+        (entry, _) => Some(entry.max(0.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Casts a ray from the origin along the x axis.
+    fn along_x(x: f32, y: f32, radius: f32) -> Option<f32> {
+        distance(0.0, 0.0, 1.0, 0.0, x, y, radius)
+    }
+
+    #[test]
+    fn rays_stop_where_they_enter_a_circle() {
+        assert_eq!(along_x(10.0, 0.0, 2.0), Some(8.0));
+        // Grazing the edge still counts.
+        assert_eq!(along_x(10.0, 2.0, 2.0), Some(10.0));
+    }
+
+    #[test]
+    fn rays_pass_circles_to_either_side() {
+        assert_eq!(along_x(10.0, 3.0, 2.0), None);
+        assert_eq!(along_x(10.0, -3.0, 2.0), None);
+    }
+
+    #[test]
+    fn rays_starting_inside_a_circle_stop_at_once() {
+        assert_eq!(along_x(1.0, 0.0, 2.0), Some(0.0));
+        assert_eq!(along_x(-1.0, 0.0, 2.0), Some(0.0));
+    }
+
+    #[test]
+    fn rays_never_meet_circles_behind_them() {
+        assert_eq!(along_x(-10.0, 0.0, 2.0), None);
+    }
+}
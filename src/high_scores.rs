@@ -0,0 +1,98 @@
+use std::{cmp::Reverse, collections::HashMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    resource::{game_mode::GameMode, score::Score},
+    storage,
+};
+
+/// High scores kept before there were player profiles. Now each profile keeps
+/// its own.
+const LEGACY_FILE: &str = "high_scores.json";
+/// Entries kept in each mode's table.
+pub const TABLE_SIZE: usize = 10;
+
+/// Three letters identifying whoever set a high score, arcade style.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Initials(pub [u8; 3]);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub initials: Initials,
+    pub score: Score,
+}
+
+/// The best scores in each game mode, highest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    tables: HashMap<GameMode, Vec<Entry>>,
+}
+
+impl Initials {
+    /// The first letters of a name, padded out with A's.
+    pub fn from_name(name: &str) -> Self {
+        let mut initials = *b"AAA";
+        for (initial, letter) in initials
+            .iter_mut()
+            .zip(name.bytes().filter(u8::is_ascii_alphabetic))
+        {
+            *initial = letter.to_ascii_uppercase();
+        }
+        Self(initials)
+    }
+}
+
+impl fmt::Display for Initials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl HighScores {
+    /// Loads the high score tables kept before there were player profiles.
+    pub fn load_legacy() -> Self {
+        let mut high_scores: Self = storage::load_or_default(LEGACY_FILE);
+        high_scores.tidy();
+        high_scores
+    }
+
+    /// Restores order after anyone edits the tables by hand.
+    pub fn tidy(&mut self) {
+        for table in self.tables.values_mut() {
+            table.sort_by_key(|entry| Reverse(entry.score));
+            table.truncate(TABLE_SIZE);
+        }
+    }
+
+    pub fn table(&self, mode: GameMode) -> &[Entry] {
+        self.tables.get(&mode).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn best(&self, mode: GameMode) -> Score {
+        self.table(mode)
+            .first()
+            .map_or_else(Score::new, |entry| entry.score)
+    }
+
+    /// Whether the given score would earn a place in the mode's table.
+    pub fn qualifies(&self, mode: GameMode, score: Score) -> bool {
+        let table = self.table(mode);
+        score > Score::new()
+            && (table.len() < TABLE_SIZE || table.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Adds an entry to the mode's table, returning its rank starting from 1,
+    /// or None if it did not make the table.
+    pub fn insert(&mut self, mode: GameMode, entry: Entry) -> Option<usize> {
+        let table = self.tables.entry(mode).or_default();
+        // Ties go to whoever got there first.
+        let index = table
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(table.len());
+        table.insert(index, entry);
+        table.truncate(TABLE_SIZE);
+        (index < TABLE_SIZE).then_some(index + 1)
+    }
+}
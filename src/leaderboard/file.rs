@@ -0,0 +1,97 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use crate::resource::game_mode::GameMode;
+
+use super::{Backend, Run};
+
+/// A leaderboard kept in a local JSON file, for playing offline or standing in
+/// for a real server.
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn load(&self) -> io::Result<Vec<Run>> {
+        match fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| io::Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Backend for FileBackend {
+    fn submit(&mut self, run: &Run) -> io::Result<()> {
+        let mut runs = self.load()?;
+        runs.push(run.clone());
+
+        // Written aside and then moved over the original, so that a crash
+        // part-way through can't lose the runs already recorded.
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&runs)?)?;
+        fs::rename(tmp, &self.path)
+    }
+
+    fn top(&mut self, mode: GameMode, count: usize) -> io::Result<Vec<Run>> {
+        let mut runs: Vec<Run> = self
+            .load()?
+            .into_iter()
+            .filter(|run| run.mode == mode)
+            .collect();
+        runs.sort_by_key(|run| Reverse(run.score));
+        runs.truncate(count);
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use crate::resource::score::Score;
+
+    use super::*;
+
+    fn run(name: &str, mode: GameMode, score: u32) -> Run {
+        Run {
+            name: name.to_owned(),
+            score: Score::from(score),
+            seed: 3,
+            mode,
+            replay: "0123456789abcdef".to_owned(),
+            ticks: 900,
+        }
+    }
+
+    #[test]
+    fn keeps_the_best_runs_for_each_mode() {
+        let dir = env::temp_dir().join(format!("rust-rocks-leaderboard-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("leaderboard.json");
+        let mut backend = FileBackend::new(&path);
+
+        backend.submit(&run("ada", GameMode::Classic, 100)).unwrap();
+        backend.submit(&run("bob", GameMode::Zen, 900)).unwrap();
+        backend.submit(&run("cy", GameMode::Classic, 300)).unwrap();
+        backend.submit(&run("di", GameMode::Classic, 200)).unwrap();
+
+        let top = backend.top(GameMode::Classic, 2).unwrap();
+        let names: Vec<_> = top.iter().map(|run| run.name.as_str()).collect();
+        assert_eq!(names, ["cy", "di"]);
+        // Nothing is left lying around from writing the file.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
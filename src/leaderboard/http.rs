@@ -0,0 +1,294 @@
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use crate::resource::game_mode::GameMode;
+
+use super::{Backend, Run};
+
+/// How long to wait on the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A leaderboard server spoken to over plain HTTP. Runs are posted as JSON to
+/// `<url>/runs`, and the best runs are fetched from
+/// `<url>/top?mode=<mode>&count=<count>`.
+pub struct HttpBackend {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl HttpBackend {
+    /// Accepts URLs of the form `http://host[:port][/path]`.
+    pub fn new(url: &str) -> io::Result<Self> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("only http:// URLs are supported"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid("bad port"))?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            path: path.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    /// Makes a request and returns the body of a successful response.
+    fn request(&self, method: &str, target: &str, body: &[u8]) -> io::Result<Vec<u8>> {
+        let address = (self.host.as_str(), self.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "could not resolve host"))?;
+        let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        // HTTP/1.0 keeps the response simple: no chunking, and the server
+        // closes the connection once it is done.
+        write!(
+            stream,
+            "{} {}{} HTTP/1.0\r\n\
+             Host: {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             \r\n",
+            method,
+            self.path,
+            target,
+            self.host,
+            body.len()
+        )?;
+        stream.write_all(body)?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+
+        let split = response
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "malformed response"))?;
+        let head = String::from_utf8_lossy(&response[..split]);
+        let status: u16 = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "malformed status line"))?;
+
+        match status {
+            200..=299 => Ok(response[split + 4..].to_vec()),
+            // The run itself was turned down. Anything else, such as a timeout
+            // or being asked to slow down, may go better later.
+            400 | 403 | 409 | 422 => Err(invalid(&format!("server answered {}", status))),
+            _ => Err(io::Error::other(format!("server answered {}", status))),
+        }
+    }
+}
+
+impl Backend for HttpBackend {
+    fn submit(&mut self, run: &Run) -> io::Result<()> {
+        self.request("POST", "/runs", &serde_json::to_vec(run)?)?;
+        Ok(())
+    }
+
+    fn top(&mut self, mode: GameMode, count: usize) -> io::Result<Vec<Run>> {
+        let mode = serde_json::to_value(mode)?;
+        let body = self.request(
+            "GET",
+            &format!("/top?mode={}&count={}", mode.as_str().unwrap(), count),
+            &[],
+        )?;
+        serde_json::from_slice(&body).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use crate::resource::score::Score;
+
+    use super::*;
+
+    /// A server which answers one request with each of the responses in turn,
+    /// then hands back the requests it was sent, as their request line and
+    /// body.
+    fn serve(responses: Vec<String>) -> (HttpBackend, JoinHandle<Vec<(String, String)>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/board/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let request = read_request(&mut reader);
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+                    request
+                })
+                .collect()
+        });
+        (HttpBackend::new(&url).unwrap(), server)
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> (String, String) {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        (
+            request_line.trim_end().to_owned(),
+            String::from_utf8(body).unwrap(),
+        )
+    }
+
+    fn respond(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.0 {}\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    fn run(name: &str, score: u32) -> Run {
+        Run {
+            name: name.to_owned(),
+            score: Score::from(score),
+            seed: 7,
+            mode: GameMode::Classic,
+            replay: "0123456789abcdef".to_owned(),
+            ticks: 1200,
+        }
+    }
+
+    #[test]
+    fn parses_urls() {
+        let backend = HttpBackend::new("http://example.com:8080/scores/").unwrap();
+        assert_eq!(backend.host, "example.com");
+        assert_eq!(backend.port, 8080);
+        assert_eq!(backend.path, "/scores");
+
+        let backend = HttpBackend::new("http://example.com").unwrap();
+        assert_eq!(backend.port, 80);
+        assert_eq!(backend.path, "");
+
+        for url in ["https://example.com", "http://:80", "http://example.com:x"] {
+            let e = HttpBackend::new(url).err().unwrap();
+            assert_eq!(e.kind(), ErrorKind::InvalidInput, "{}", url);
+        }
+    }
+
+    #[test]
+    fn submit_posts_the_run() {
+        let (mut backend, server) = serve(vec![respond("201 Created", "")]);
+
+        backend.submit(&run("ada", 1500)).unwrap();
+
+        let requests = server.join().unwrap();
+        let (request_line, body) = &requests[0];
+        assert_eq!(request_line, "POST /board/runs HTTP/1.0");
+        let posted: Run = serde_json::from_str(body).unwrap();
+        assert_eq!(posted.name, "ada");
+        assert_eq!(posted.score, Score::from(1500));
+    }
+
+    #[test]
+    fn refused_runs_are_not_retried() {
+        let refusals = [
+            "400 Bad Request",
+            "403 Forbidden",
+            "409 Conflict",
+            "422 Unprocessable Entity",
+        ];
+        let (mut backend, server) =
+            serve(refusals.iter().map(|status| respond(status, "")).collect());
+
+        for status in refusals {
+            let refused = backend.submit(&run("ada", 1500)).unwrap_err();
+            assert_eq!(refused.kind(), ErrorKind::InvalidInput, "{}", status);
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn runs_are_retried_when_the_server_is_struggling() {
+        let failures = [
+            "404 Not Found",
+            "408 Request Timeout",
+            "429 Too Many Requests",
+            "500 Internal Server Error",
+            "503 Service Unavailable",
+        ];
+        let (mut backend, server) =
+            serve(failures.iter().map(|status| respond(status, "")).collect());
+
+        for status in failures {
+            let failed = backend.submit(&run("ada", 1500)).unwrap_err();
+            assert_ne!(failed.kind(), ErrorKind::InvalidInput, "{}", status);
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn top_fetches_runs_for_the_mode() {
+        let runs = serde_json::to_string(&[run("ada", 2000), run("bob", 1000)]).unwrap();
+        let (mut backend, server) = serve(vec![respond("200 OK", &runs)]);
+
+        let top = backend.top(GameMode::TimeAttack, 2).unwrap();
+        let names: Vec<_> = top.iter().map(|run| run.name.as_str()).collect();
+        assert_eq!(names, ["ada", "bob"]);
+
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests[0].0,
+            "GET /board/top?mode=TimeAttack&count=2 HTTP/1.0"
+        );
+    }
+
+    #[test]
+    fn malformed_responses_are_errors() {
+        let (mut backend, server) = serve(vec!["nonsense".to_owned()]);
+
+        let e = backend.submit(&run("ada", 1500)).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+mod file;
+mod http;
+
+use std::{
+    env, io,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    replay::Replay,
+    resource::{game_mode::GameMode, score::Score},
+    storage,
+};
+
+pub use file::FileBackend;
+pub use http::HttpBackend;
+
+/// Environment variable naming the leaderboard to use, either an
+/// `http://host:port/path` URL or a `file:path` to a local file. The
+/// leaderboard is disabled when unset.
+const ENDPOINT_VAR: &str = "RUST_ROCKS_LEADERBOARD";
+/// Runs not yet accepted by the leaderboard, kept across restarts.
+const QUEUE_FILE: &str = "leaderboard_queue.json";
+/// How long to wait before trying queued runs again.
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A finished game, as submitted to the leaderboard.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Run {
+    pub name: String,
+    pub score: Score,
+    pub seed: u64,
+    pub mode: GameMode,
+    /// Hash of the game's replay, by which the score can be checked.
+    pub replay: String,
+    /// Number of updates the game lasted.
+    pub ticks: u64,
+}
+
+/// Where runs are kept. Implementations may block, since they are only used
+/// from the leaderboard's own thread.
+pub trait Backend: Send {
+    /// Records a run. Fails with `ErrorKind::InvalidInput` if the run was
+    /// refused outright, in which case it is not worth trying again.
+    fn submit(&mut self, run: &Run) -> io::Result<()>;
+
+    /// The best runs in the given mode, highest score first.
+    fn top(&mut self, mode: GameMode, count: usize) -> io::Result<Vec<Run>>;
+}
+
+enum Command {
+    Submit(Run),
+    Top {
+        mode: GameMode,
+        count: usize,
+        reply: Sender<io::Result<Vec<Run>>>,
+    },
+}
+
+/// Talks to a leaderboard in the background, so that a slow or unreachable
+/// server never holds up the game. Runs which cannot be submitted are queued
+/// and tried again later.
+pub struct Leaderboard {
+    commands: Sender<Command>,
+}
+
+impl Run {
+    pub fn new(name: &str, score: Score, replay: &Replay) -> Self {
+        Self {
+            name: name.to_owned(),
+            score,
+            seed: replay.seed,
+            mode: replay.mode,
+            replay: replay.hash(),
+            ticks: replay.ticks(),
+        }
+    }
+}
+
+impl Leaderboard {
+    pub fn new(backend: Box<dyn Backend>) -> Self {
+        let (commands, receiver) = mpsc::channel();
+        thread::spawn(move || serve(backend, receiver));
+        Self { commands }
+    }
+
+    /// Connects to the leaderboard named by the environment, if any.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = env::var(ENDPOINT_VAR).ok()?;
+        let backend: Box<dyn Backend> = if let Some(path) = endpoint.strip_prefix("file:") {
+            Box::new(FileBackend::new(path))
+        } else {
+            match HttpBackend::new(&endpoint) {
+                Ok(backend) => Box::new(backend),
+                Err(e) => {
+                    log::error!("Could not use leaderboard {}: {}", endpoint, e);
+                    return None;
+                }
+            }
+        };
+        Some(Self::new(backend))
+    }
+
+    pub fn submit(&self, run: Run) {
+        self.send(Command::Submit(run));
+    }
+
+    /// Fetches the best runs in the given mode. The answer arrives on the
+    /// returned channel once the leaderboard responds.
+    pub fn top(&self, mode: GameMode, count: usize) -> Receiver<io::Result<Vec<Run>>> {
+        let (reply, receiver) = mpsc::channel();
+        self.send(Command::Top { mode, count, reply });
+        receiver
+    }
+
+    fn send(&self, command: Command) {
+        if self.commands.send(command).is_err() {
+            log::error!("The leaderboard has stopped");
+        }
+    }
+}
+
+/// Carries out commands until the leaderboard is dropped, retrying queued runs
+/// whenever things are quiet.
+fn serve(mut backend: Box<dyn Backend>, commands: Receiver<Command>) {
+    let mut queue: Vec<Run> = storage::load_or_default(QUEUE_FILE);
+    loop {
+        if flush(backend.as_mut(), &mut queue) {
+            save_queue(&queue);
+        }
+
+        match commands.recv_timeout(RETRY_INTERVAL) {
+            Ok(Command::Submit(run)) => {
+                queue.push(run);
+                save_queue(&queue);
+            }
+            Ok(Command::Top { mode, count, reply }) => {
+                // Nobody may be waiting for the answer any more.
+                let _ = reply.send(backend.top(mode, count));
+            }
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Submits queued runs in order, stopping at the first which can't be
+/// submitted right now. Returns whether any runs left the queue.
+fn flush(backend: &mut dyn Backend, queue: &mut Vec<Run>) -> bool {
+    let queued = queue.len();
+    while let Some(run) = queue.first() {
+        match backend.submit(run) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput => {
+                log::error!("The leaderboard refused a run: {}", e);
+            }
+            Err(e) => {
+                log::warn!("Could not submit run, will try again later: {}", e);
+                break;
+            }
+        }
+        queue.remove(0);
+    }
+    queue.len() != queued
+}
+
+fn save_queue(queue: &[Run]) {
+    if let Err(e) = storage::save(QUEUE_FILE, &queue) {
+        log::error!("Could not save leaderboard queue: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// Answers each submission with the next of its prepared results, and
+    /// keeps the runs it accepts.
+    struct Scripted {
+        results: VecDeque<io::Result<()>>,
+        accepted: Vec<String>,
+    }
+
+    impl Scripted {
+        fn new(results: Vec<io::Result<()>>) -> Self {
+            Self {
+                results: results.into(),
+                accepted: Vec::new(),
+            }
+        }
+    }
+
+    impl Backend for Scripted {
+        fn submit(&mut self, run: &Run) -> io::Result<()> {
+            let result = self.results.pop_front().unwrap_or(Ok(()));
+            if result.is_ok() {
+                self.accepted.push(run.name.clone());
+            }
+            result
+        }
+
+        fn top(&mut self, _: GameMode, _: usize) -> io::Result<Vec<Run>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn run(name: &str) -> Run {
+        Run {
+            name: name.to_owned(),
+            score: Score::from(100),
+            seed: 1,
+            mode: GameMode::Classic,
+            replay: "0123456789abcdef".to_owned(),
+            ticks: 600,
+        }
+    }
+
+    fn names(queue: &[Run]) -> Vec<&str> {
+        queue.iter().map(|run| run.name.as_str()).collect()
+    }
+
+    #[test]
+    fn flush_submits_queued_runs_in_order() {
+        let mut backend = Scripted::new(Vec::new());
+        let mut queue = vec![run("a"), run("b"), run("c")];
+
+        assert!(flush(&mut backend, &mut queue));
+        assert!(queue.is_empty());
+        assert_eq!(backend.accepted, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn flush_keeps_runs_which_fail_for_later() {
+        let unreachable = io::Error::new(io::ErrorKind::ConnectionRefused, "down");
+        let mut backend = Scripted::new(vec![Ok(()), Err(unreachable)]);
+        let mut queue = vec![run("a"), run("b"), run("c")];
+
+        assert!(flush(&mut backend, &mut queue));
+        assert_eq!(names(&queue), ["b", "c"]);
+
+        // Once the leaderboard is back, the rest go through in order.
+        assert!(flush(&mut backend, &mut queue));
+        assert!(queue.is_empty());
+        assert_eq!(backend.accepted, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn flush_drops_refused_runs() {
+        let refused = io::Error::new(io::ErrorKind::InvalidInput, "cheated");
+        let mut backend = Scripted::new(vec![Err(refused)]);
+        let mut queue = vec![run("a"), run("b")];
+
+        assert!(flush(&mut backend, &mut queue));
+        assert!(queue.is_empty());
+        assert_eq!(backend.accepted, ["b"]);
+    }
+
+    #[test]
+    fn flush_reports_when_nothing_left_the_queue() {
+        let unreachable = io::Error::new(io::ErrorKind::TimedOut, "slow");
+        let mut backend = Scripted::new(vec![Err(unreachable)]);
+        let mut queue = vec![run("a")];
+
+        assert!(!flush(&mut backend, &mut queue));
+        assert_eq!(names(&queue), ["a"]);
+        assert!(!flush(&mut backend, &mut Vec::new()));
+    }
+}
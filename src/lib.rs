@@ -0,0 +1,23 @@
+#![feature(iter_intersperse)]
+
+pub mod atlas;
+pub mod autopilot;
+pub mod balance;
+pub mod bot;
+#[macro_use]
+pub mod component;
+pub mod daily;
+#[macro_use]
+pub mod controller;
+pub mod font;
+pub mod gym;
+pub mod high_scores;
+pub mod leaderboard;
+pub mod netplay;
+pub mod profile;
+pub mod replay;
+pub mod resource;
+pub mod scene;
+pub mod spectate;
+pub mod storage;
+pub mod verify;
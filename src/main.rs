@@ -1,32 +1,95 @@
-#![feature(iter_intersperse)]
-
-mod component;
 mod fps_counter;
-#[macro_use]
-mod controller;
-mod font;
-mod resource;
-mod scene;
 
 use std::{
+    env,
     error::Error,
+    net::TcpListener,
     rc::Rc,
     sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use fps_counter::FpsCounter;
 use freetype::Library;
 use opengl_graphics::{GlGraphics, OpenGL};
 use piston::{CloseEvent, EventSettings, Events, RenderEvent, UpdateEvent, WindowSettings};
-use resource::{bounds::Bounds, score::Score};
-use scene::{GameScene, Scene, SceneEvent, TitleScene};
+use rust_rocks::{
+    bot::Bot,
+    daily::Date,
+    font::Font,
+    high_scores::{Entry, Initials},
+    leaderboard::{Leaderboard, Run},
+    netplay::Session,
+    profile::Profiles,
+    resource::{bounds::Bounds, game_mode::GameMode, textures},
+    scene::{
+        DailyScene, GameScene, InitialsScene, ProfileScene, Scene, SceneEvent, ScoreScene,
+        TitleScene,
+    },
+    spectate::{Broadcaster, Spectating},
+};
 use sdl2_window::Sdl2Window;
 
-use crate::{font::Font, resource::textures, scene::ScoreScene};
+/// How long to wait for the other player to start their game of versus.
+const VERSUS_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long to wait for a game to start at the broadcaster.
+const SPECTATE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Sprites drawn by the benchmark, unless told otherwise.
+const BENCHMARK_SPRITES: usize = 5_000;
+/// Frames per second the benchmark may draw, high enough to be no limit.
+const BENCHMARK_MAX_FPS: u64 = 10_000;
+const USAGE: &str = "usage: rust-rocks [--versus <local address> <peer address> | \
+                     --spectate <address> | --broadcast <address> | --bot <address> | \
+                     --benchmark [<sprites>]]";
+
+/// What the game opens with, as asked for on the command line.
+enum Start {
+    Title,
+    Versus(Session),
+    Spectate(Box<Spectating>),
+    /// A game of classic, flown by a bot.
+    Bot(Bot),
+    /// A demo crowded with the given number of sprites, timing each frame.
+    Benchmark(usize),
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
 
+    // `--versus <local address> <peer address>` plays against another machine,
+    // `--spectate <address>` watches the games broadcast from one,
+    // `--broadcast <address>` lets others watch the games played here,
+    // `--bot <address>` hands the ship over to a program connecting there, and
+    // `--benchmark [<sprites>]` times drawing with the arena full of sprites.
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (start, broadcaster) = match args.as_slice() {
+        [] => (Start::Title, None),
+        [flag, local, peer] if flag == "--versus" => {
+            log::info!("Waiting for the other player at {}...", peer);
+            let session = Session::connect(local, peer, VERSUS_TIMEOUT)?;
+            (Start::Versus(session), None)
+        }
+        [flag, addr] if flag == "--spectate" => {
+            log::info!("Waiting for a game at {}...", addr);
+            let spectating = Spectating::connect(addr.as_str(), SPECTATE_TIMEOUT)?;
+            (Start::Spectate(Box::new(spectating)), None)
+        }
+        [flag, addr] if flag == "--broadcast" => {
+            (Start::Title, Some(Broadcaster::bind(addr.as_str())?))
+        }
+        [flag, addr] if flag == "--bot" => {
+            log::info!("Waiting for a bot at {}...", addr);
+            let bot = Bot::accept(&TcpListener::bind(addr)?)?;
+            (Start::Bot(bot), None)
+        }
+        [flag] if flag == "--benchmark" => (Start::Benchmark(BENCHMARK_SPRITES), None),
+        [flag, sprites] if flag == "--benchmark" => match sprites.parse() {
+            Ok(sprites) => (Start::Benchmark(sprites), None),
+            Err(_) => return Err(USAGE.into()),
+        },
+        _ => return Err(USAGE.into()),
+    };
+
     let bounds = Bounds::new(0.0, 0.0, 800.0, 600.0);
 
     let opengl = OpenGL::V3_2;
@@ -37,7 +100,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         .build()?;
     let mut gl = GlGraphics::new(opengl);
 
-    let mut events = Events::new(EventSettings::new());
+    // The benchmark draws as fast as it can, rather than at the usual rate.
+    let mut event_settings = EventSettings::new();
+    if let Start::Benchmark(_) = start {
+        event_settings.max_fps = BENCHMARK_MAX_FPS;
+    }
+    let mut events = Events::new(event_settings);
 
     let (sender, receiver) = mpsc::channel::<SceneEvent>();
     let sender = Arc::new(Mutex::new(sender));
@@ -50,13 +118,56 @@ fn main() -> Result<(), Box<dyn Error>> {
     let font = Rc::new(Font::new(&face).unwrap());
     let textures = Rc::new(textures::load_textures());
 
-    let mut scene: Box<dyn Scene> = Box::new(TitleScene::new(
-        Arc::clone(&sender),
-        Rc::clone(&textures),
-        Rc::clone(&font),
-    ));
-
-    let mut high_score = Score::new();
+    let mut profiles = Profiles::load();
+    let leaderboard = Leaderboard::from_env();
+    let mut scene: Box<dyn Scene> = match start {
+        Start::Versus(session) => Box::new(GameScene::versus(
+            Rc::clone(&textures),
+            Rc::clone(&font),
+            bounds,
+            profiles.current(),
+            session,
+            Arc::clone(&sender),
+        )),
+        Start::Spectate(spectating) => Box::new(GameScene::spectate(
+            Rc::clone(&textures),
+            Rc::clone(&font),
+            profiles.current(),
+            *spectating,
+            Arc::clone(&sender),
+        )),
+        Start::Bot(bot) => Box::new(
+            GameScene::new(
+                Rc::clone(&textures),
+                Rc::clone(&font),
+                bounds,
+                profiles.current(),
+                GameMode::Classic,
+                rand::random(),
+                Arc::clone(&sender),
+            )
+            .with_bot(bot),
+        ),
+        Start::Benchmark(sprites) => Box::new(GameScene::benchmark(
+            Rc::clone(&textures),
+            Rc::clone(&font),
+            bounds,
+            profiles.current(),
+            sprites,
+            Arc::clone(&sender),
+        )),
+        Start::Title => Box::new(TitleScene::new(
+            Arc::clone(&sender),
+            Rc::clone(&textures),
+            Rc::clone(&font),
+            profiles.current(),
+        )),
+    };
+
+    // The date of the daily challenge in progress, if any.
+    let mut daily_date = None;
+    // When the game in progress began, for the player's statistics.
+    let mut game_start = Instant::now();
     let mut fps = FpsCounter::new();
 
     // This is a fixed-step event loop. Delta time is constant, but updates-per-
@@ -64,6 +175,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     // and render once per second.
     'main: while let Some(e) = events.next(&mut window) {
         if let Some(_) = e.close_args() {
+            scene.on_quit();
             break;
         }
 
@@ -71,6 +183,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             .try_iter()
             .any(|event| matches!(event, SceneEvent::Quit))
         {
+            scene.on_quit();
             break;
         }
 
@@ -88,34 +201,209 @@ fn main() -> Result<(), Box<dyn Error>> {
         for event in receiver.try_iter() {
             match event {
                 SceneEvent::Quit => {
+                    scene.on_quit();
                     break 'main;
                 }
-                SceneEvent::GoToGame => {
-                    scene = Box::new(GameScene::new(
+                SceneEvent::Continue => {
+                    game_start = Instant::now();
+                    scene = match GameScene::resume(
                         Rc::clone(&textures),
-                        bounds,
+                        Rc::clone(&font),
+                        &mut profiles,
                         Arc::clone(&sender),
-                    ))
+                    ) {
+                        Ok(game) => Box::new(game.with_broadcast(broadcaster.as_ref())),
+                        Err(e) => {
+                            log::error!("Could not continue game: {}", e);
+                            GameScene::set_aside_save();
+                            Box::new(
+                                TitleScene::new(
+                                    Arc::clone(&sender),
+                                    Rc::clone(&textures),
+                                    Rc::clone(&font),
+                                    profiles.current(),
+                                )
+                                .with_notice(e.to_string()),
+                            )
+                        }
+                    }
                 }
-                SceneEvent::PlayerHit { current_score } => {
-                    scene = Box::new(ScoreScene::new(
-                        Arc::clone(&sender),
-                        Rc::clone(&font),
-                        high_score,
-                        current_score,
-                    ));
+                SceneEvent::GoToGame { mode } => {
+                    let seed = if mode == GameMode::Daily {
+                        let today = Date::today();
+                        profiles.current_mut().daily.begin(today);
+                        profiles.save();
+                        daily_date = Some(today);
+                        today.seed()
+                    } else {
+                        rand::random()
+                    };
+
+                    game_start = Instant::now();
+                    scene = Box::new(
+                        GameScene::new(
+                            Rc::clone(&textures),
+                            Rc::clone(&font),
+                            bounds,
+                            profiles.current(),
+                            mode,
+                            seed,
+                            Arc::clone(&sender),
+                        )
+                        .with_broadcast(broadcaster.as_ref()),
+                    )
+                }
+                SceneEvent::GameOver {
+                    mode,
+                    current_score,
+                    breakdown,
+                } => {
+                    scene.on_game_over();
+                    // Games the player had help with are kept off the records.
+                    let is_assisted = scene.replay().is_some_and(|replay| replay.assisted);
+                    let profile = profiles.current_mut();
+                    if !is_assisted {
+                        profile
+                            .stats
+                            .record(current_score, &breakdown, game_start.elapsed());
+                    }
+                    if let Some(date) = daily_date.take() {
+                        profile.daily.finish(date, current_score);
+                    }
 
-                    if current_score > high_score {
-                        high_score = current_score;
+                    if let Some(replay) = scene.replay() {
+                        if let Err(e) = replay.save() {
+                            log::error!("Could not save replay: {}", e);
+                        }
+                        if let Some(leaderboard) = leaderboard.as_ref().filter(|_| !is_assisted) {
+                            leaderboard.submit(Run::new(&profile.name, current_score, replay));
+                        }
                     }
+
+                    let high_scores = &profile.high_scores;
+                    scene = if !is_assisted && high_scores.qualifies(mode, current_score) {
+                        Box::new(InitialsScene::new(
+                            Arc::clone(&sender),
+                            Rc::clone(&font),
+                            mode,
+                            current_score,
+                            breakdown,
+                            Initials::from_name(&profile.name),
+                        ))
+                    } else {
+                        Box::new(
+                            ScoreScene::new(
+                                Arc::clone(&sender),
+                                Rc::clone(&font),
+                                mode,
+                                high_scores.best(mode),
+                                current_score,
+                                breakdown,
+                                None,
+                            )
+                            .with_leaderboard(leaderboard.as_ref()),
+                        )
+                    };
+                    profiles.save();
+                }
+                SceneEvent::SignHighScore {
+                    mode,
+                    initials,
+                    current_score,
+                    breakdown,
+                } => {
+                    let high_scores = &mut profiles.current_mut().high_scores;
+                    let high_score = high_scores.best(mode);
+                    let rank = high_scores.insert(
+                        mode,
+                        Entry {
+                            initials,
+                            score: current_score,
+                        },
+                    );
+                    profiles.save();
+
+                    scene = Box::new(
+                        ScoreScene::new(
+                            Arc::clone(&sender),
+                            Rc::clone(&font),
+                            mode,
+                            high_score,
+                            current_score,
+                            breakdown,
+                            rank,
+                        )
+                        .with_leaderboard(leaderboard.as_ref()),
+                    );
+                }
+                SceneEvent::VersusOver { outcome } => {
+                    scene = Box::new(
+                        TitleScene::new(
+                            Arc::clone(&sender),
+                            Rc::clone(&textures),
+                            Rc::clone(&font),
+                            profiles.current(),
+                        )
+                        .with_notice(outcome.message().to_owned()),
+                    )
+                }
+                SceneEvent::SpectatingOver { ending } => {
+                    scene = Box::new(
+                        TitleScene::new(
+                            Arc::clone(&sender),
+                            Rc::clone(&textures),
+                            Rc::clone(&font),
+                            profiles.current(),
+                        )
+                        .with_notice(ending.message()),
+                    )
                 }
                 SceneEvent::GoToTitle => {
                     scene = Box::new(TitleScene::new(
                         Arc::clone(&sender),
                         Rc::clone(&textures),
                         Rc::clone(&font),
+                        profiles.current(),
+                    ))
+                }
+                SceneEvent::GoToDemo => {
+                    scene = Box::new(GameScene::demo(
+                        Rc::clone(&textures),
+                        Rc::clone(&font),
+                        bounds,
+                        profiles.current(),
+                        Arc::clone(&sender),
+                    ))
+                }
+                SceneEvent::GoToDaily => {
+                    scene = Box::new(DailyScene::new(
+                        Arc::clone(&sender),
+                        Rc::clone(&font),
+                        profiles.current().daily.clone(),
+                    ))
+                }
+                SceneEvent::GoToProfiles => {
+                    scene = Box::new(ProfileScene::new(
+                        Arc::clone(&sender),
+                        Rc::clone(&font),
+                        profiles.clone(),
                     ))
                 }
+                SceneEvent::ToggleGraphics => {
+                    let profile = profiles.current_mut();
+                    profile.graphics = profile.graphics.toggled();
+                    profiles.save();
+                }
+                SceneEvent::SelectProfile { index } => {
+                    profiles.select(index);
+                    profiles.save();
+                    sender.lock().unwrap().send(SceneEvent::GoToTitle).unwrap();
+                }
+                SceneEvent::CreateProfile { name } => {
+                    profiles.create(&String::from_utf8_lossy(&name));
+                    profiles.save();
+                    sender.lock().unwrap().send(SceneEvent::GoToTitle).unwrap();
+                }
             }
         }
     }
@@ -0,0 +1,36 @@
+mod rollback;
+mod session;
+
+pub use rollback::Rollback;
+pub use session::Session;
+
+/// How a game of versus ended, for the local player.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    Draw,
+    /// The other player stopped answering.
+    Disconnected,
+}
+
+impl Outcome {
+    /// How the game went for the player in the given seat, given the seats
+    /// still flying when it ended.
+    pub fn of(seat: u8, survivors: &[u8]) -> Self {
+        match (survivors.contains(&seat), survivors.len()) {
+            (true, 1) => Outcome::Won,
+            (false, 1) => Outcome::Lost,
+            _ => Outcome::Draw,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Outcome::Won => "You won!",
+            Outcome::Lost => "You lost!",
+            Outcome::Draw => "It's a draw!",
+            Outcome::Disconnected => "Lost contact with the other player",
+        }
+    }
+}
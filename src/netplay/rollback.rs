@@ -0,0 +1,355 @@
+use std::{
+    collections::VecDeque,
+    sync::mpsc::Receiver,
+    time::{Duration, Instant},
+};
+
+use legion::IntoQuery;
+
+use crate::{
+    component::PlayerInput,
+    controller::ControllerState,
+    scene::{SceneEvent, Simulation, Snapshot},
+};
+
+use super::{
+    session::{Message, Session},
+    Outcome,
+};
+
+/// Updates between reading the local controller and acting on it, giving the
+/// local input time to reach the peer before it is needed there.
+const INPUT_DELAY: u64 = 2;
+/// How far the game may run ahead of the peer's inputs on guesswork alone
+/// before it waits for them to catch up.
+const MAX_PREDICTION: u64 = 12;
+/// Most inputs sent in one message.
+const MAX_BATCH: u64 = 64;
+/// How long the peer may stay silent before they are given up on.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How many times to send the last inputs when leaving, so that the peer
+/// surely hears the end of the game too.
+const FAREWELLS: usize = 10;
+
+/// Keeps a two-player game in step across the network. The peer's inputs are
+/// predicted until they arrive; when a prediction proves wrong, the game is
+/// rewound to where it went wrong and played forward again with the right
+/// inputs.
+pub struct Rollback {
+    session: Session,
+    /// Events from the simulation, which might not stand if the game is
+    /// rewound.
+    events: Receiver<SceneEvent>,
+    /// The next update to simulate.
+    tick: u64,
+    /// Local inputs, by update. These run ahead of `tick` by the input delay.
+    local: Vec<ControllerState>,
+    /// The peer's inputs received so far, by update.
+    remote: Vec<ControllerState>,
+    /// The peer's inputs which each update was simulated with, whether
+    /// received or predicted.
+    assumed: Vec<ControllerState>,
+    /// How many local inputs the peer has received.
+    delivered: u64,
+    /// The simulation at the start of each update which may yet be rewound.
+    snapshots: VecDeque<(u64, Snapshot)>,
+    /// The earliest update whose prediction proved wrong.
+    rewind_to: Option<u64>,
+    /// The update on which the game ended, and the seats still flying then.
+    over: Option<(u64, Vec<u8>)>,
+    last_heard: Instant,
+}
+
+impl Rollback {
+    /// Takes over a session whose simulation reports events on the given
+    /// receiver.
+    pub fn new(session: Session, events: Receiver<SceneEvent>) -> Self {
+        Self {
+            session,
+            events,
+            tick: 0,
+            local: vec![ControllerState::new(); INPUT_DELAY as usize],
+            remote: Vec::new(),
+            assumed: Vec::new(),
+            delivered: 0,
+            snapshots: VecDeque::new(),
+            rewind_to: None,
+            over: None,
+            last_heard: Instant::now(),
+        }
+    }
+
+    pub fn seat(&self) -> u8 {
+        self.session.seat
+    }
+
+    /// Advances the game by one update with the local controller, correcting
+    /// any mispredictions along the way. Returns the outcome once the game is
+    /// over on both machines.
+    pub fn advance(
+        &mut self,
+        sim: &mut Simulation,
+        dt: Duration,
+        controller: ControllerState,
+    ) -> Option<Outcome> {
+        self.receive();
+        if self.last_heard.elapsed() > DISCONNECT_TIMEOUT {
+            return Some(Outcome::Disconnected);
+        }
+
+        if let Some(tick) = self.rewind_to.take() {
+            self.rewind(sim, tick, dt);
+        }
+
+        // Wait for the peer rather than guess too far ahead of them.
+        let confirmed = self.remote.len() as u64;
+        if self.tick < confirmed + MAX_PREDICTION {
+            self.local.push(controller);
+            self.simulate(sim, dt);
+        }
+
+        self.send_inputs();
+        self.snapshots.retain(|(tick, _)| *tick >= confirmed);
+
+        match &self.over {
+            Some((tick, seats)) if *tick < confirmed => {
+                for _ in 0..FAREWELLS {
+                    self.send_inputs();
+                }
+                Some(Outcome::of(self.session.seat, seats))
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes in everything the peer has sent, noting any update which was
+    /// simulated with the wrong guess.
+    fn receive(&mut self) {
+        while let Some(message) = self.session.recv() {
+            self.last_heard = Instant::now();
+            if let Message::Inputs {
+                start,
+                inputs,
+                received,
+            } = message
+            {
+                self.delivered = self.delivered.max(received);
+                for (tick, input) in (start..).zip(inputs) {
+                    if tick != self.remote.len() as u64 {
+                        continue;
+                    }
+                    self.remote.push(input);
+                    if self.assumed.get(tick as usize).is_some_and(|x| *x != input) {
+                        self.rewind_to = Some(self.rewind_to.map_or(tick, |t| t.min(tick)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends the local inputs the peer has not yet received.
+    fn send_inputs(&self) {
+        let start = self.delivered;
+        let end = (self.local.len() as u64).min(start + MAX_BATCH);
+        self.session.send(&Message::Inputs {
+            start,
+            inputs: self.local[start as usize..end as usize].to_vec(),
+            received: self.remote.len() as u64,
+        });
+    }
+
+    /// Returns the game to the start of the given update and plays it forward
+    /// to where it was.
+    fn rewind(&mut self, sim: &mut Simulation, tick: u64, dt: Duration) {
+        let snapshot = match self.snapshots.iter().position(|(t, _)| *t == tick) {
+            Some(i) => self.snapshots.split_off(i).pop_front().unwrap().1,
+            None => {
+                log::error!("Cannot rewind to update {}", tick);
+                return;
+            }
+        };
+
+        sim.restore(&snapshot);
+        if self.over.as_ref().is_some_and(|(t, _)| *t >= tick) {
+            self.over = None;
+        }
+
+        let now = self.tick;
+        self.tick = tick;
+        while self.tick < now {
+            self.simulate(sim, dt);
+        }
+    }
+
+    /// Simulates the next update, guessing that the peer is still doing
+    /// whatever they were last known to be doing if their input has not
+    /// arrived.
+    fn simulate(&mut self, sim: &mut Simulation, dt: Duration) {
+        let tick = self.tick as usize;
+        let remote = match self.remote.get(tick) {
+            Some(input) => *input,
+            None => self.remote.last().copied().unwrap_or_default(),
+        };
+        self.assumed.truncate(tick);
+        self.assumed.push(remote);
+
+        let local = self.local[tick];
+        let controllers = if self.session.seat == 0 {
+            [local, remote]
+        } else {
+            [remote, local]
+        };
+
+        self.snapshots.push_back((self.tick, sim.snapshot()));
+        sim.step(dt, &controllers);
+
+        let is_over = self
+            .events
+            .try_iter()
+            .filter(|event| matches!(event, SceneEvent::GameOver { .. }))
+            .count()
+            > 0;
+        if is_over && self.over.is_none() {
+            let seats = <&PlayerInput>::query()
+                .iter(&sim.world)
+                .map(|input| input.seat)
+                .collect();
+            self.over = Some((self.tick, seats));
+        }
+
+        self.tick += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::UdpSocket,
+        sync::{mpsc, Arc, Mutex},
+        thread,
+    };
+
+    use crate::{
+        bot::TIMESTEP,
+        component::Spatial,
+        resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+    };
+
+    use super::*;
+
+    /// Updates each player plays.
+    const TICKS: u64 = 240;
+
+    /// What the player in the seat does for the given update: something
+    /// different every few updates, so that predictions keep going wrong.
+    fn script(seat: u8, tick: u64) -> ControllerState {
+        let phase = (tick / (4 + seat as u64)) % 4;
+        ControllerState {
+            left_thumb: [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)][phase as usize],
+            right_thumb: (0.0, if seat == 0 { 1.0 } else { -1.0 }),
+            switch_weapon: false,
+            fire: phase.is_multiple_of(2),
+        }
+    }
+
+    fn simulation(seed: u64) -> (Simulation, Receiver<SceneEvent>) {
+        let (sender, events) = mpsc::channel();
+        let sim = Simulation::new(
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+            Tuning::new(),
+            GameMode::Versus,
+            seed,
+            Arc::new(Mutex::new(sender)),
+        );
+        (sim, events)
+    }
+
+    /// A pair of sessions connected to one another over loopback.
+    fn sessions() -> (Session, Session) {
+        let free = || {
+            let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+            socket.local_addr().unwrap()
+        };
+        let (a, b) = (free(), free());
+        let other = thread::spawn(move || Session::connect(b, a, Duration::from_secs(5)));
+        let session = Session::connect(a, b, Duration::from_secs(5)).unwrap();
+        (session, other.join().unwrap().unwrap())
+    }
+
+    /// Where each ship is and which way it faces, by seat.
+    fn ships(sim: &Simulation) -> Vec<(u8, f32, f32, f32)> {
+        let mut ships: Vec<_> = <(&PlayerInput, &Spatial)>::query()
+            .iter(&sim.world)
+            .map(|(input, spatial)| (input.seat, spatial.x, spatial.y, spatial.angle_o))
+            .collect();
+        ships.sort_by_key(|(seat, ..)| *seat);
+        ships
+    }
+
+    #[test]
+    fn sessions_agree_on_the_game() {
+        let (a, b) = sessions();
+        assert_eq!(a.seed, b.seed);
+        assert_ne!(a.seat, b.seat);
+    }
+
+    #[test]
+    fn both_players_play_the_same_game() {
+        let (a, b) = sessions();
+        let seed = a.seed;
+        let mut players: Vec<_> = vec![a, b]
+            .into_iter()
+            .map(|session| {
+                let (sim, events) = simulation(seed);
+                (Rollback::new(session, events), sim)
+            })
+            .collect();
+
+        // Each player runs a few updates ahead of the other in turn, so both
+        // have to guess at the other's inputs and then correct themselves.
+        while players.iter().any(|(rollback, _)| rollback.tick < TICKS) {
+            for (rollback, sim) in &mut players {
+                for _ in 0..3 {
+                    if rollback.tick >= TICKS {
+                        break;
+                    }
+                    let seat = rollback.seat();
+                    let controller = script(seat, rollback.local.len() as u64);
+                    rollback.advance(sim, TIMESTEP, controller);
+                }
+            }
+        }
+
+        // Wait for the last inputs to arrive, and correct for them.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        for (rollback, sim) in &mut players {
+            while (rollback.remote.len() as u64) < TICKS && Instant::now() < deadline {
+                rollback.receive();
+                thread::sleep(Duration::from_millis(1));
+            }
+            if let Some(tick) = rollback.rewind_to.take() {
+                rollback.rewind(sim, tick, TIMESTEP);
+            }
+        }
+
+        // The same game played at one machine, with every input known.
+        let (mut expected, _events) = simulation(seed);
+        for tick in 0..TICKS {
+            let controllers = [0, 1].map(|seat| {
+                if tick < INPUT_DELAY {
+                    ControllerState::new()
+                } else {
+                    script(seat, tick)
+                }
+            });
+            expected.step(TIMESTEP, &controllers);
+        }
+
+        for (rollback, sim) in &players {
+            assert_eq!(rollback.remote.len() as u64, TICKS + INPUT_DELAY);
+            assert_eq!(sim.replay.hash(), expected.replay.hash());
+            assert_eq!(ships(sim), ships(&expected));
+            assert_eq!(sim.score(), expected.score());
+        }
+    }
+}
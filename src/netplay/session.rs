@@ -0,0 +1,157 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::controller::ControllerState;
+
+/// How often to greet the peer while waiting for them to answer.
+const HELLO_INTERVAL: Duration = Duration::from_millis(100);
+/// Large enough for any message we send.
+const MAX_DATAGRAM: usize = 65_507;
+
+/// Everything the peers say to each other.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Message {
+    /// Introduces a peer. Whichever peer picked the lower nonce takes the first
+    /// seat, and the nonces together seed the game.
+    Hello {
+        nonce: u64,
+        /// Whether this peer has heard the other's hello.
+        heard: bool,
+    },
+    /// The sender's inputs for consecutive updates, starting with `start`.
+    Inputs {
+        start: u64,
+        inputs: Vec<ControllerState>,
+        /// How many of the recipient's inputs the sender has received.
+        received: u64,
+    },
+}
+
+/// A connection to the other player's machine.
+pub struct Session {
+    socket: UdpSocket,
+    nonce: u64,
+    /// The seat of the local player.
+    pub seat: u8,
+    /// The seed both players' games are played from.
+    pub seed: u64,
+}
+
+impl Session {
+    /// Waits for the peer at the given address to connect, up to the timeout.
+    /// Both players must connect to one another.
+    pub fn connect(
+        local: impl ToSocketAddrs,
+        peer: impl ToSocketAddrs,
+        timeout: Duration,
+    ) -> io::Result<Self> {
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(peer)?;
+        socket.set_read_timeout(Some(HELLO_INTERVAL))?;
+
+        let mut nonce: u64 = rand::random();
+        let mut theirs = None;
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            // The peer may not be listening yet, in which case sending fails
+            // until they are.
+            let hello = Message::Hello {
+                nonce,
+                heard: theirs.is_some(),
+            };
+            match send(&socket, &hello) {
+                Err(e) if !is_transient(&e) => return Err(e),
+                _ => (),
+            }
+
+            match recv(&socket) {
+                Ok(Some(Message::Hello {
+                    nonce: their_nonce,
+                    heard,
+                })) => {
+                    if their_nonce == nonce {
+                        // Neither could take the first seat; pick again.
+                        nonce = rand::random();
+                        theirs = None;
+                        continue;
+                    }
+                    theirs = Some(their_nonce);
+                    if heard {
+                        break;
+                    }
+                }
+                Ok(_) => (),
+                Err(e) if is_transient(&e) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let theirs = match theirs {
+            Some(theirs) if Instant::now() < deadline => theirs,
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::TimedOut,
+                    "the other player did not answer",
+                ))
+            }
+        };
+
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            nonce,
+            seat: if nonce < theirs { 0 } else { 1 },
+            seed: nonce ^ theirs,
+        })
+    }
+
+    pub fn send(&self, message: &Message) {
+        if let Err(e) = send(&self.socket, message) {
+            log::debug!("Could not reach the other player: {}", e);
+        }
+    }
+
+    /// The next message from the peer, if one has arrived. Greetings from a
+    /// peer still waiting to hear back are answered here.
+    pub fn recv(&self) -> Option<Message> {
+        loop {
+            match recv(&self.socket) {
+                Ok(Some(Message::Hello { .. })) => self.send(&Message::Hello {
+                    nonce: self.nonce,
+                    heard: true,
+                }),
+                Ok(Some(message)) => return Some(message),
+                Ok(None) => (),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return None,
+                Err(e) => {
+                    log::debug!("Could not hear the other player: {}", e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+fn send(socket: &UdpSocket, message: &Message) -> io::Result<()> {
+    socket.send(&serde_json::to_vec(message)?).map(|_| ())
+}
+
+/// Receives a message, or None if the datagram was not one.
+fn recv(socket: &UdpSocket) -> io::Result<Option<Message>> {
+    let mut buffer = vec![0; MAX_DATAGRAM];
+    let len = socket.recv(&mut buffer)?;
+    Ok(serde_json::from_slice(&buffer[..len]).ok())
+}
+
+/// Errors which only mean that nothing has arrived yet.
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::ConnectionRefused
+    )
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    daily::History,
+    high_scores::HighScores,
+    resource::{score::Score, scoring::Breakdown, tuning::Tuning},
+    storage,
+};
+
+const PROFILES_FILE: &str = "profiles.json";
+/// Longest name a profile may have.
+pub const NAME_LENGTH: usize = 8;
+const DEFAULT_NAME: &str = "PLAYER";
+
+/// Controller buttons for in-game actions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bindings {
+    pub fire: u8,
+    pub switch_weapon: u8,
+    pub pause: u8,
+    pub finish: u8,
+    /// Hands the ship over to the autopilot, and back.
+    pub assist: u8,
+}
+
+/// How the game is drawn.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Graphics {
+    /// Bitmap sprites.
+    #[default]
+    Sprites,
+    /// Glowing line art, in the manner of the vector arcade machines.
+    Vector,
+}
+
+/// Totals across every game a player has finished.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub total_score: u64,
+    pub kills: u32,
+    pub near_misses: u32,
+    pub best_multiplier: u32,
+    pub time_played: Duration,
+}
+
+/// One player's high scores, settings, statistics and daily challenges.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub high_scores: HighScores,
+    #[serde(default)]
+    pub settings: Tuning,
+    #[serde(default)]
+    pub bindings: Bindings,
+    #[serde(default)]
+    pub graphics: Graphics,
+    #[serde(default)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub daily: History,
+}
+
+/// Every player profile on this machine, and which one is playing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    pub current: usize,
+}
+
+impl Graphics {
+    /// The other way of drawing the game.
+    pub fn toggled(self) -> Self {
+        match self {
+            Graphics::Sprites => Graphics::Vector,
+            Graphics::Vector => Graphics::Sprites,
+        }
+    }
+
+    /// How the choice is shown in menus.
+    pub fn label(self) -> &'static str {
+        match self {
+            Graphics::Sprites => "Graphics: Sprites",
+            Graphics::Vector => "Graphics: Vector",
+        }
+    }
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self {
+            fire: 5,
+            switch_weapon: 4,
+            pause: 7,
+            finish: 6,
+            assist: 3,
+        }
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    /// Adds a finished game to the totals.
+    pub fn record(&mut self, score: Score, breakdown: &Breakdown, time_played: Duration) {
+        self.games_played += 1;
+        self.total_score += score.points() as u64;
+        self.kills += breakdown.kills;
+        self.near_misses += breakdown.near_misses;
+        self.best_multiplier = self.best_multiplier.max(breakdown.best_multiplier);
+        self.time_played += time_played;
+    }
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            high_scores: HighScores::default(),
+            settings: Tuning::new(),
+            bindings: Bindings::new(),
+            graphics: Graphics::Sprites,
+            stats: Stats::default(),
+            daily: History::default(),
+        }
+    }
+}
+
+impl Profiles {
+    /// Loads every profile. On first run, a default profile inherits any high
+    /// scores and daily challenges from before there were profiles.
+    pub fn load() -> Self {
+        let mut profiles = storage::load_or_default::<Self>(PROFILES_FILE);
+        if profiles.profiles.is_empty() {
+            profiles = Self::default();
+        }
+        for profile in &mut profiles.profiles {
+            profile.high_scores.tidy();
+        }
+        profiles.current = profiles.current.min(profiles.profiles.len() - 1);
+        profiles
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = storage::save(PROFILES_FILE, self) {
+            log::error!("Could not save profiles: {}", e);
+        }
+    }
+
+    pub fn current(&self) -> &Profile {
+        &self.profiles[self.current]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.current]
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.profiles.len() {
+            self.current = index;
+        }
+    }
+
+    /// Adds a profile and switches to it. Names are trimmed, and a profile
+    /// with a name already in use is selected instead of duplicated.
+    pub fn create(&mut self, name: &str) {
+        let name = name.trim();
+        let name = if name.is_empty() { DEFAULT_NAME } else { name };
+        match self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)
+        {
+            Some(index) => self.current = index,
+            None => {
+                self.profiles.push(Profile::new(name));
+                self.current = self.profiles.len() - 1;
+            }
+        }
+    }
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![Profile {
+                high_scores: HighScores::load_legacy(),
+                daily: History::load_legacy(),
+                ..Profile::new(DEFAULT_NAME)
+            }],
+            current: 0,
+        }
+    }
+}
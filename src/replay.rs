@@ -0,0 +1,153 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controller::ControllerState,
+    resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+    storage,
+};
+
+/// Bumped whenever the layout of a replay changes, or the simulation changes
+/// so that the same inputs play out differently.
+pub const REPLAY_VERSION: u32 = 9;
+
+/// Everything needed to play a game over again: the conditions it started
+/// under, and the state of every player's controller at every update. Since the simulation is
+/// deterministic, the same replay always produces the same game.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub mode: GameMode,
+    pub tuning: Tuning,
+    /// Size of the window the game was played in.
+    pub bounds: Bounds,
+    /// Time between updates. Zero until the first update is recorded.
+    pub timestep: Duration,
+    /// Controller states, run-length encoded, since the controllers rarely
+    /// change from one update to the next.
+    pub inputs: Vec<Input>,
+    /// Whether the player was helped along at any point, which keeps the
+    /// game off the records.
+    #[serde(default)]
+    pub assisted: bool,
+}
+
+/// Controller states held for a number of consecutive updates, one for each
+/// seat.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Input {
+    pub ticks: u32,
+    pub controllers: Vec<ControllerState>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, mode: GameMode, tuning: Tuning, bounds: Bounds) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            mode,
+            tuning,
+            bounds,
+            timestep: Duration::ZERO,
+            inputs: Vec::new(),
+            assisted: false,
+        }
+    }
+
+    /// Records the controller states of one update.
+    pub fn record(&mut self, dt: Duration, controllers: &[ControllerState]) {
+        if self.timestep == Duration::ZERO {
+            self.timestep = dt;
+        } else if dt != self.timestep {
+            log::warn!("Update took {:?}, not {:?}", dt, self.timestep);
+        }
+
+        match self.inputs.last_mut() {
+            Some(last) if last.controllers == controllers => last.ticks += 1,
+            _ => self.inputs.push(Input {
+                ticks: 1,
+                controllers: controllers.to_vec(),
+            }),
+        }
+    }
+
+    /// Forgets every update after the given number, so that they can be
+    /// recorded again differently.
+    pub fn truncate(&mut self, ticks: u64) {
+        let mut kept = 0;
+        let mut len = 0;
+        for input in &mut self.inputs {
+            if kept >= ticks {
+                break;
+            }
+            input.ticks = input.ticks.min((ticks - kept) as u32);
+            kept += input.ticks as u64;
+            len += 1;
+        }
+        self.inputs.truncate(len);
+    }
+
+    /// Adds updates recorded elsewhere, such as by a game being watched.
+    pub fn extend(&mut self, inputs: impl IntoIterator<Item = Input>) {
+        for input in inputs {
+            match self.inputs.last_mut() {
+                Some(last) if last.controllers == input.controllers => last.ticks += input.ticks,
+                _ => self.inputs.push(input),
+            }
+        }
+    }
+
+    /// The updates recorded after the given number of updates.
+    pub fn since(&self, ticks: u64) -> Vec<Input> {
+        let mut skipped = 0;
+        let mut inputs = Vec::new();
+        for input in &self.inputs {
+            let end = skipped + input.ticks as u64;
+            if end > ticks {
+                inputs.push(Input {
+                    ticks: (end - ticks.max(skipped)) as u32,
+                    controllers: input.controllers.clone(),
+                });
+            }
+            skipped = end;
+        }
+        inputs
+    }
+
+    /// The number of updates recorded.
+    pub fn ticks(&self) -> u64 {
+        self.inputs.iter().map(|input| input.ticks as u64).sum()
+    }
+
+    /// Identifies this replay, so that a submitted score can be matched with
+    /// the game which earned it.
+    pub fn hash(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap();
+        format!("{:016x}", fnv1a(&bytes))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Keeps the replay in the data directory, named after its hash.
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(&format!("replays/{}.json", self.hash()), self)
+    }
+}
+
+/// The 64-bit FNV-1a hash, which is stable across platforms and versions of
+/// Rust, unlike the standard library's hashers.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
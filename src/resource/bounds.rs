@@ -1,15 +1,16 @@
 use piston::Size;
+use serde::{Deserialize, Serialize};
 
 const WRAP_PAD: f32 = 64.0;
 const DWRAP_PAD: f32 = 128.0;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Bound {
     pub p0: Point,
     pub p1: Point,
@@ -17,7 +18,7 @@ pub struct Bound {
     pub height: f32,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Bounds {
     pub inner: Bound,
     pub outer: Bound,
@@ -0,0 +1,94 @@
+use graphics::{Context, Transformed};
+use serde::{Deserialize, Serialize};
+
+use super::bounds::Bounds;
+
+/// Furthest the view is displaced, in pixels, at maximum trauma.
+const MAX_SHAKE_OFFSET: f64 = 24.0;
+/// Furthest the view is rotated, in radians, at maximum trauma.
+const MAX_SHAKE_ANGLE: f64 = 0.06;
+/// Trauma lost per second.
+const TRAUMA_DECAY: f32 = 1.2;
+/// How quickly the camera catches up to its target, as a fraction of the
+/// remaining distance per second.
+const FOLLOW_RATE: f32 = 6.0;
+
+/// The view through which the world is rendered. The camera is centered on
+/// (x, y) in world coordinates and sees a window-sized region of the world.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Camera {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub zoom: f32,
+    /// Rotation of the view, in radians.
+    pub rotation: f32,
+    /// How shaken up the view is, from 0 (steady) to 1 (violently shaking).
+    /// Shake grows with the square of trauma, so small knocks are subtle.
+    pub trauma: f32,
+    /// Seconds of simulation the camera has observed, which drives the shake.
+    pub elapsed: f32,
+}
+
+impl Camera {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            zoom: 1.0,
+            rotation: 0.0,
+            trauma: 0.0,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Moves the camera toward the target, keeping the view within the arena
+    /// so that nothing beyond its edge is shown, and lets trauma subside.
+    pub fn update(&mut self, target: (f32, f32), arena: &Bounds, dt: f32) {
+        let t = (FOLLOW_RATE * dt).min(1.0);
+        self.x += (target.0 - self.x) * t;
+        self.y += (target.1 - self.y) * t;
+
+        let (half_w, half_h) = (self.width / 2.0 / self.zoom, self.height / 2.0 / self.zoom);
+        self.x = clamp_within(self.x, arena.inner.p0.x, arena.inner.p1.x, half_w);
+        self.y = clamp_within(self.y, arena.inner.p0.y, arena.inner.p1.y, half_h);
+
+        self.trauma = (self.trauma - TRAUMA_DECAY * dt).max(0.0);
+        self.elapsed += dt;
+    }
+
+    /// Transforms the given context from screen to world coordinates.
+    pub fn view(&self, c: &Context) -> Context {
+        let (width, height) = (self.width as f64, self.height as f64);
+        let shake = (self.trauma * self.trauma) as f64;
+        let t = self.elapsed as f64;
+        // Sums of incommensurate sines make a cheap, smooth, deterministic
+        // noise.
+        let noise = |seed: f64| ((t * 37.0 + seed).sin() + (t * 23.0 + seed * 2.0).sin()) / 2.0;
+
+        c.trans(width / 2.0, height / 2.0)
+            .rot_rad(self.rotation as f64 + MAX_SHAKE_ANGLE * shake * noise(1.0))
+            .zoom(self.zoom as f64)
+            .trans(
+                -self.x as f64 + MAX_SHAKE_OFFSET * shake * noise(2.0),
+                -self.y as f64 + MAX_SHAKE_OFFSET * shake * noise(3.0),
+            )
+    }
+}
+
+/// Clamps a camera coordinate so the view's half-extent stays within [lo, hi].
+/// If the arena is narrower than the view, the view is centered on the arena.
+fn clamp_within(value: f32, lo: f32, hi: f32, half_extent: f32) -> f32 {
+    if hi - lo <= half_extent * 2.0 {
+        (lo + hi) / 2.0
+    } else {
+        value.clamp(lo + half_extent, hi - half_extent)
+    }
+}
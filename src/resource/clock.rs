@@ -1,15 +1,62 @@
-use std::time::{Duration, Instant};
+use std::{
+    ops::{Add, AddAssign, Sub},
+    time::Duration,
+};
 
-#[derive(Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+/// A moment in game time, measured from the start of the game. Game time only
+/// advances as the simulation does, so unlike `std::time::Instant` it can be
+/// saved and restored.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+pub struct Instant(Duration);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Clock {
+    pub start: Instant,
     pub now: Instant,
     pub dt: Duration,
 }
 
+impl Instant {
+    /// The duration from an earlier instant to this one, or zero if the other
+    /// instant is later.
+    pub fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant(self.0 + rhs)
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Saturates at zero, like `std::time::Instant`.
+    fn sub(self, rhs: Instant) -> Duration {
+        self.saturating_duration_since(rhs)
+    }
+}
+
 impl Clock {
     pub fn new() -> Self {
+        let now = Instant::default();
         Clock {
-            now: Instant::now(),
+            start: now,
+            now,
             dt: Duration::ZERO,
         }
     }
@@ -18,4 +65,15 @@ impl Clock {
         self.now += dt;
         self.dt = dt;
     }
+
+    /// Time elapsed since the clock was started.
+    pub fn elapsed(&self) -> Duration {
+        self.now - self.start
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
 }
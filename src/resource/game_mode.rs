@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// The rules a game is played by.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Destroy as many asteroids as possible before the ship is destroyed.
+    Classic,
+    /// Score as much as possible before time runs out.
+    TimeAttack,
+    /// Dodge an ever-growing field of asteroids, without the aid of guns.
+    Survival,
+    /// Practice without fear of dying. The game ends when the player chooses.
+    Zen,
+    /// Classic rules, but everyone faces the same asteroids on the same day.
+    Daily,
+    /// Two players on different machines try to outlast each other, and can
+    /// shoot each other down.
+    Versus,
+}
+
+const TIME_ATTACK_LIMIT: Duration = Duration::from_secs(120);
+/// How often another asteroid joins the field in survival mode.
+const SURVIVAL_ESCALATION: Duration = Duration::from_secs(10);
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::Survival => "Survival",
+            GameMode::Zen => "Zen",
+            GameMode::Daily => "Daily Challenge",
+            GameMode::Versus => "Versus",
+        }
+    }
+
+    /// The number of players in the game.
+    pub fn seats(&self) -> u8 {
+        match self {
+            GameMode::Versus => 2,
+            _ => 1,
+        }
+    }
+
+    /// How long the game lasts, if it is timed.
+    pub fn time_limit(&self) -> Option<Duration> {
+        match self {
+            GameMode::TimeAttack => Some(TIME_ATTACK_LIMIT),
+            _ => None,
+        }
+    }
+
+    pub fn can_shoot(&self) -> bool {
+        !matches!(self, GameMode::Survival)
+    }
+
+    /// Bosses only appear in modes where the player is able to fight them,
+    /// and would only get in the way of players fighting each other.
+    pub fn has_bosses(&self) -> bool {
+        self.can_shoot() && self.seats() == 1
+    }
+
+    pub fn can_die(&self) -> bool {
+        !matches!(self, GameMode::Zen)
+    }
+
+    /// Whether the player ends the game when they choose, since it has no
+    /// other end.
+    pub fn can_finish(&self) -> bool {
+        !self.can_die() && self.time_limit().is_none()
+    }
+
+    /// Whether the player is scored on how long they stay alive, rather than
+    /// on what they destroy.
+    pub fn scores_survival(&self) -> bool {
+        matches!(self, GameMode::Survival)
+    }
+
+    /// How many asteroids should be in play after the given time, in a game
+    /// which starts with the given number.
+    pub fn asteroid_count(&self, base: usize, elapsed: Duration) -> usize {
+        match self {
+            GameMode::Survival => {
+                base + (elapsed.as_secs() / SURVIVAL_ESCALATION.as_secs()) as usize
+            }
+            _ => base,
+        }
+    }
+}
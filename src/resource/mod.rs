@@ -1,4 +1,11 @@
 pub mod bounds;
+pub mod camera;
 pub mod clock;
+pub mod game_mode;
+pub mod particle_budget;
+pub mod random;
 pub mod score;
+pub mod scoring;
 pub mod textures;
+pub mod tuning;
+pub mod wave;
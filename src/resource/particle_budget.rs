@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// The most particles which may be in play at once. Emitters stop emitting
+/// while the budget is exhausted, which keeps frame times bounded.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ParticleBudget {
+    pub max: usize,
+}
+
+impl ParticleBudget {
+    pub fn new() -> Self {
+        Self { max: 1_500 }
+    }
+}
+
+impl Default for ParticleBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,25 @@
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+use serde::{Deserialize, Serialize};
+
+/// The source of all randomness in the simulation. Games started from the same
+/// seed play out identically given identical input.
+pub type Random = Pcg32;
+
+/// The source of randomness for effects with no bearing on play, such as
+/// particles. Kept apart from `Random`, so that the effects can change without
+/// changing how games play out.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Cosmetic(pub Random);
+
+pub fn seeded(seed: u64) -> Random {
+    Random::seed_from_u64(seed)
+}
+
+impl Cosmetic {
+    /// Seeded from the game's seed, but differently to the game's own source,
+    /// so that the two don't follow one another.
+    pub fn seeded(seed: u64) -> Self {
+        Self(Random::seed_from_u64(!seed))
+    }
+}
@@ -1,12 +1,30 @@
 use std::ops::AddAssign;
 
-#[derive(Copy, Clone, Debug, Eq, PartialOrd, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub struct Score(u32);
 
 impl Score {
     pub fn new() -> Self {
         Self(0)
     }
+
+    pub fn points(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<u32> for Score {
+    fn from(points: u32) -> Self {
+        Self(points)
+    }
 }
 
 impl std::fmt::Display for Score {
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{clock::Instant, score::Score};
+
+/// Kills made within this window of one another extend the current chain.
+const COMBO_WINDOW: Duration = Duration::from_secs(2);
+/// Chained kills needed to raise the multiplier by one.
+const KILLS_PER_STEP: u32 = 3;
+const MAX_MULTIPLIER: u32 = 8;
+/// Base value of narrowly avoiding an asteroid.
+pub const NEAR_MISS_VALUE: u32 = 1;
+
+/// Tracks kill chains and how the score was earned over a game.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Scoring {
+    chain: u32,
+    last_kill: Option<Instant>,
+    pub breakdown: Breakdown,
+}
+
+/// How a final score was earned, for display on the score screen.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Breakdown {
+    pub kills: u32,
+    /// Points from kills before multipliers were applied.
+    pub base: u32,
+    /// Points from kills due to multipliers.
+    pub combo_bonus: u32,
+    pub near_misses: u32,
+    pub near_miss_bonus: u32,
+    pub best_multiplier: u32,
+    /// Projectiles fired by the players.
+    pub shots: u32,
+    /// Projectiles fired by the players which struck something.
+    pub hits: u32,
+    /// Asteroids destroyed, by any means, fragments included.
+    pub asteroids: u32,
+}
+
+impl Breakdown {
+    /// The fraction of the players' shots which struck something, if they
+    /// fired any.
+    pub fn accuracy(&self) -> Option<f32> {
+        match self.shots {
+            0 => None,
+            shots => Some(self.hits as f32 / shots as f32),
+        }
+    }
+}
+
+impl Scoring {
+    pub fn new() -> Self {
+        Self {
+            chain: 0,
+            last_kill: None,
+            breakdown: Breakdown {
+                best_multiplier: 1,
+                ..Breakdown::default()
+            },
+        }
+    }
+
+    /// The multiplier which would apply to a kill made at the given time.
+    pub fn multiplier(&self, now: Instant) -> u32 {
+        if self.is_chain_broken(now) {
+            1
+        } else {
+            (1 + self.chain / KILLS_PER_STEP).min(MAX_MULTIPLIER)
+        }
+    }
+
+    /// Awards points for destroying a target of the given base value, and
+    /// extends the kill chain. Returns the points awarded and the multiplier
+    /// applied.
+    pub fn kill(&mut self, score: &mut Score, value: u32, now: Instant) -> (u32, u32) {
+        let multiplier = self.multiplier(now);
+        if self.is_chain_broken(now) {
+            self.chain = 0;
+        }
+        self.chain += 1;
+        self.last_kill = Some(now);
+
+        let points = value * multiplier;
+        *score += points;
+
+        let breakdown = &mut self.breakdown;
+        breakdown.kills += 1;
+        breakdown.base += value;
+        breakdown.combo_bonus += points - value;
+        breakdown.best_multiplier = breakdown.best_multiplier.max(multiplier);
+
+        (points, multiplier)
+    }
+
+    /// Awards points for narrowly avoiding a collision. Near misses benefit
+    /// from, but do not extend, the kill chain. Returns the points awarded and
+    /// the multiplier applied.
+    pub fn near_miss(&mut self, score: &mut Score, now: Instant) -> (u32, u32) {
+        let multiplier = self.multiplier(now);
+        let points = NEAR_MISS_VALUE * multiplier;
+        *score += points;
+
+        self.breakdown.near_misses += 1;
+        self.breakdown.near_miss_bonus += points;
+
+        (points, multiplier)
+    }
+
+    fn is_chain_broken(&self, now: Instant) -> bool {
+        match self.last_kill {
+            Some(last) => now > last + COMBO_WINDOW,
+            None => true,
+        }
+    }
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,20 +1,27 @@
-use std::collections::HashMap;
+use image::RgbaImage;
 
-use opengl_graphics::{Texture, TextureSettings};
+use crate::{
+    atlas::{Atlas, Packer},
+    component::SpriteKind,
+};
 
-use crate::component::SpriteKind;
+/// Size of the pages sprites are packed onto, which fits all of them.
+const PAGE_SIZE: u32 = 1024;
 
-pub type Textures = HashMap<SpriteKind, Texture>;
+pub type Textures = Atlas<SpriteKind>;
 
 pub fn load_textures() -> Textures {
-    let mut textures = HashMap::new();
-    textures.insert(SpriteKind::Asteroid, load_texture("asteroid.bmp"));
-    textures.insert(SpriteKind::Player, load_texture("player-ship.bmp"));
-    textures.insert(SpriteKind::Bullet, load_texture("bullet.bmp"));
-    textures.insert(SpriteKind::Title, load_texture("title.bmp"));
-    textures
+    let mut packer = Packer::new(PAGE_SIZE);
+    packer.add(SpriteKind::Asteroid, load_image("asteroid.bmp"));
+    packer.add(SpriteKind::Boss, load_image("asteroid.bmp"));
+    packer.add(SpriteKind::Player, load_image("player-ship.bmp"));
+    packer.add(SpriteKind::Bullet, load_image("bullet.bmp"));
+    packer.add(SpriteKind::Title, load_image("title.bmp"));
+    Atlas::new(packer).unwrap()
 }
 
-fn load_texture(name: &str) -> Texture {
-    Texture::from_path("./resources/".to_owned() + name, &TextureSettings::new()).unwrap()
+fn load_image(name: &str) -> RgbaImage {
+    image::open("./resources/".to_owned() + name)
+        .unwrap()
+        .to_rgba8()
 }
@@ -0,0 +1,276 @@
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::component::{Hazard, HazardKind};
+
+/// Fire rates the players' weapons can be tuned to. Beyond them cooldowns
+/// shrink to nothing, or grow longer than a `Duration` can hold.
+pub const FIRE_RATES: RangeInclusive<f32> = 0.1..=10.0;
+/// Arena sizes, relative to the window, the game can be played in. Much
+/// smaller and there is no room to dodge; much larger and the asteroids are
+/// too spread out to find.
+pub const ARENA_SCALES: RangeInclusive<f32> = 0.5..=4.0;
+/// Asteroid counts the game can keep in play without slowing to a crawl.
+pub const ASTEROID_COUNTS: RangeInclusive<usize> = 0..=100;
+/// Asteroid speeds the game can be played at. Beyond them asteroids cross the
+/// arena between updates.
+pub const ASTEROID_SPEEDS: RangeInclusive<f32> = 0.0..=10.0;
+/// Most hazards a wave can bring.
+pub const MAX_HAZARDS: u32 = 16;
+/// Heaviest a kind of hazard can be weighted, so that the weights can be added
+/// up without overflowing.
+pub const MAX_HAZARD_WEIGHT: u32 = 1_000;
+
+/// Gameplay settings which alter the rules of the simulation rather than its
+/// presentation.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tuning {
+    /// When true, physical bodies (such as asteroids) bounce off of one
+    /// another instead of passing through each other.
+    pub elastic_collisions: bool,
+    /// Size of the arena relative to the window. When greater than one, the
+    /// camera scrolls to follow the player.
+    pub arena_scale: f32,
+    /// When true, later waves bring environmental hazards such as gravity
+    /// wells and nebulae.
+    pub hazards: bool,
+    /// Which hazards later waves bring, and how many.
+    pub hazard_layout: HazardLayout,
+    /// Asteroids in play at the start of a game, and kept in play after.
+    pub asteroids: usize,
+    /// Multiplies the speed asteroids enter the arena at.
+    pub asteroid_speed: f32,
+    /// Multiplies how often the players' weapons can fire.
+    pub fire_rate: f32,
+}
+
+impl Tuning {
+    pub fn new() -> Self {
+        Self {
+            elastic_collisions: true,
+            arena_scale: 1.0,
+            hazards: true,
+            hazard_layout: HazardLayout::new(),
+            asteroids: 5,
+            asteroid_speed: 1.0,
+            fire_rate: 1.0,
+        }
+    }
+
+    /// The tuning with any setting the simulation can't play by, such as one
+    /// edited by hand into a profile or a save, brought back into range.
+    pub fn clamped(self) -> Self {
+        let defaults = Self::new();
+        Self {
+            arena_scale: clamp(self.arena_scale, &ARENA_SCALES, defaults.arena_scale),
+            hazard_layout: self.hazard_layout.clamped(),
+            asteroids: self
+                .asteroids
+                .clamp(*ASTEROID_COUNTS.start(), *ASTEROID_COUNTS.end()),
+            asteroid_speed: clamp(
+                self.asteroid_speed,
+                &ASTEROID_SPEEDS,
+                defaults.asteroid_speed,
+            ),
+            fire_rate: clamp(self.fire_rate, &FIRE_RATES, defaults.fire_rate),
+            ..self
+        }
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How hazards are laid out over the waves of a game. Regular waves bring one
+/// more hazard every few waves, up to a limit, each picked at random from the
+/// kinds by weight. Boss waves are always clear.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HazardLayout {
+    /// Waves played for each hazard a wave brings. With two, waves 2 and 3
+    /// bring one hazard, waves 4 and 5 bring two, and so on.
+    pub waves_per_hazard: u32,
+    /// Most hazards in play at once.
+    pub max: u32,
+    /// Each kind of hazard, as it is placed, and its chance of being picked
+    /// relative to the others.
+    pub kinds: [(Hazard, u32); 3],
+}
+
+impl HazardLayout {
+    pub fn new() -> Self {
+        Self {
+            waves_per_hazard: 2,
+            max: 4,
+            kinds: [
+                (
+                    Hazard {
+                        kind: HazardKind::GravityWell {
+                            strength: 4_000_000.0,
+                        },
+                        radius: 320.0,
+                    },
+                    1,
+                ),
+                (
+                    Hazard {
+                        kind: HazardKind::Repulsor {
+                            strength: 3_000_000.0,
+                        },
+                        radius: 240.0,
+                    },
+                    1,
+                ),
+                (
+                    Hazard {
+                        kind: HazardKind::Nebula { drag: 1.5 },
+                        radius: 160.0,
+                    },
+                    1,
+                ),
+            ],
+        }
+    }
+
+    /// How many hazards the given wave brings, if it isn't a boss wave.
+    pub fn count(&self, wave: u32) -> u32 {
+        (wave / self.waves_per_hazard).min(self.max)
+    }
+
+    /// Picks a kind of hazard by weight, unless every kind is weighted zero.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<Hazard> {
+        let total: u32 = self.kinds.iter().map(|&(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+        for &(hazard, weight) in &self.kinds {
+            if roll < weight {
+                return Some(hazard);
+            }
+            roll -= weight;
+        }
+        unreachable!("the roll is less than the total weight")
+    }
+
+    /// The layout with any hazard which can't be played by put back as it was
+    /// by default, and the counts and weights brought into range.
+    fn clamped(self) -> Self {
+        let defaults = Self::new();
+        let mut kinds = self.kinds;
+        for ((hazard, weight), &(default, _)) in kinds.iter_mut().zip(&defaults.kinds) {
+            let strength = match hazard.kind {
+                HazardKind::GravityWell { strength } | HazardKind::Repulsor { strength } => {
+                    strength
+                }
+                HazardKind::Nebula { drag } => drag,
+            };
+            if !(hazard.radius.is_finite() && hazard.radius > 0.0 && strength.is_finite()) {
+                *hazard = default;
+            }
+            *weight = (*weight).min(MAX_HAZARD_WEIGHT);
+        }
+        Self {
+            waves_per_hazard: self.waves_per_hazard.max(1),
+            max: self.max.min(MAX_HAZARDS),
+            kinds,
+        }
+    }
+}
+
+impl Default for HazardLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Brings a setting into range, or back to its default if it isn't a number.
+fn clamp(value: f32, range: &RangeInclusive<f32>, default: f32) -> f32 {
+    if value.is_nan() {
+        default
+    } else {
+        value.clamp(*range.start(), *range.end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_out_of_range_are_clamped() {
+        let tuning = Tuning {
+            arena_scale: -1.0,
+            asteroids: usize::MAX,
+            asteroid_speed: f32::INFINITY,
+            fire_rate: 0.0,
+            ..Tuning::new()
+        }
+        .clamped();
+        assert_eq!(tuning.arena_scale, *ARENA_SCALES.start());
+        assert_eq!(tuning.asteroids, *ASTEROID_COUNTS.end());
+        assert_eq!(tuning.asteroid_speed, *ASTEROID_SPEEDS.end());
+        assert_eq!(tuning.fire_rate, *FIRE_RATES.start());
+    }
+
+    #[test]
+    fn settings_which_are_not_numbers_are_reset() {
+        let tuning = Tuning {
+            arena_scale: f32::NAN,
+            asteroid_speed: f32::NAN,
+            fire_rate: f32::NAN,
+            ..Tuning::new()
+        }
+        .clamped();
+        let defaults = Tuning::new();
+        assert_eq!(tuning.arena_scale, defaults.arena_scale);
+        assert_eq!(tuning.asteroid_speed, defaults.asteroid_speed);
+        assert_eq!(tuning.fire_rate, defaults.fire_rate);
+    }
+
+    #[test]
+    fn hazards_are_picked_by_weight() {
+        let mut layout = HazardLayout::new();
+        layout.kinds[0].1 = 0;
+        layout.kinds[2].1 = 0;
+        let mut rng = crate::resource::random::seeded(1);
+        for _ in 0..100 {
+            let hazard = layout.pick(&mut rng).unwrap();
+            assert!(matches!(hazard.kind, HazardKind::Repulsor { .. }));
+        }
+
+        layout.kinds[1].1 = 0;
+        assert!(layout.pick(&mut rng).is_none());
+    }
+
+    #[test]
+    fn hazard_layouts_out_of_range_are_clamped() {
+        let mut layout = HazardLayout {
+            waves_per_hazard: 0,
+            max: u32::MAX,
+            ..HazardLayout::new()
+        };
+        layout.kinds[0].0.radius = f32::NAN;
+        layout.kinds[1].1 = u32::MAX;
+        let layout = Tuning {
+            hazard_layout: layout,
+            ..Tuning::new()
+        }
+        .clamped()
+        .hazard_layout;
+
+        assert_eq!(layout.count(u32::MAX), MAX_HAZARDS);
+        assert_eq!(
+            layout.kinds[0].0.radius,
+            HazardLayout::new().kinds[0].0.radius
+        );
+        assert_eq!(layout.kinds[1].1, MAX_HAZARD_WEIGHT);
+    }
+}
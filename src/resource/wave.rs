@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// Kills needed to clear a regular wave.
+const KILLS_PER_WAVE: u32 = 12;
+/// A boss appears in place of every nth wave.
+const BOSS_EVERY: u32 = 3;
+
+/// Progress through the waves of a game. Regular waves are cleared by
+/// destroying enough asteroids; boss waves are cleared by defeating the boss.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Wave {
+    pub number: u32,
+    pub kills: u32,
+    pub boss: bool,
+}
+
+impl Wave {
+    pub fn new() -> Self {
+        Self {
+            number: 1,
+            kills: 0,
+            boss: false,
+        }
+    }
+
+    pub fn record_kill(&mut self) {
+        if !self.boss {
+            self.kills += 1;
+        }
+    }
+
+    /// Whether the current regular wave has been cleared.
+    pub fn is_cleared(&self) -> bool {
+        !self.boss && self.kills >= KILLS_PER_WAVE
+    }
+
+    /// Advances to the next wave. Returns true if it is a boss wave.
+    pub fn advance(&mut self) -> bool {
+        self.number += 1;
+        self.kills = 0;
+        self.boss = self.number.is_multiple_of(BOSS_EVERY);
+        self.boss
+    }
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    rc::Rc,
+    sync::{mpsc::Sender, Arc, Mutex},
+};
+
+use graphics::color;
+use opengl_graphics::GlGraphics;
+use piston::{ButtonEvent, Event, HatState, RenderArgs, UpdateArgs};
+
+use crate::{
+    daily::{Date, History},
+    font::{Font, Layout},
+    resource::game_mode::GameMode,
+};
+
+use super::{
+    menu::{on_button_press, on_hat_press, Menu},
+    Scene, SceneEvent,
+};
+
+/// Number of past attempts to list.
+const HISTORY_LENGTH: usize = 7;
+
+/// Shows today's daily challenge and the player's past attempts, and lets the
+/// player start today's attempt if they have not already.
+pub struct DailyScene {
+    font: Rc<Font>,
+    bus: Arc<Mutex<Sender<SceneEvent>>>,
+    today: Date,
+    history: History,
+    menu: Menu<2>,
+}
+
+impl DailyScene {
+    pub fn new(bus: Arc<Mutex<Sender<SceneEvent>>>, font: Rc<Font>, history: History) -> Self {
+        Self {
+            font,
+            bus,
+            today: Date::today(),
+            history,
+            menu: Menu {
+                choices: [
+                    (
+                        "Play",
+                        SceneEvent::GoToGame {
+                            mode: GameMode::Daily,
+                        },
+                    ),
+                    ("Back", SceneEvent::GoToTitle),
+                ],
+                cursor_index: 0,
+            },
+        }
+    }
+
+    fn is_played(&self) -> bool {
+        self.history.attempt(self.today).is_some()
+    }
+}
+
+impl Scene for DailyScene {
+    fn update(&mut self, _args: UpdateArgs) {}
+
+    fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
+        let mut lines = vec![
+            format!("Daily Challenge {}", self.today),
+            match self.history.attempt(self.today) {
+                Some(attempt) => format!("Today's score: {}", attempt.score),
+                None => "Not yet attempted".to_owned(),
+            },
+            String::new(),
+        ];
+
+        lines.extend(
+            self.history
+                .attempts
+                .iter()
+                .rev()
+                .filter(|attempt| attempt.date != self.today)
+                .take(HISTORY_LENGTH)
+                .map(|attempt| format!("{}  {}", attempt.date, attempt.score)),
+        );
+        lines.push(String::new());
+
+        if self.is_played() {
+            lines.push("Come back tomorrow!".to_owned());
+            lines.push("> Back <".to_owned());
+        } else {
+            lines.extend(self.menu.labels());
+        }
+
+        let lines = lines
+            .iter()
+            .map(|line| self.font.create_text(line).unwrap())
+            .collect();
+
+        gl.draw(args.viewport(), |c, g| {
+            use graphics::clear;
+
+            clear([0.0; 4], g);
+
+            let center = {
+                let [_, _, width, height] = args.viewport().rect;
+                (width as f64 / 2.0, height as f64 / 2.0)
+            };
+
+            self.font
+                .render_lines(&lines, Layout::Centered, center, color::WHITE, &c, g)
+                .unwrap();
+        });
+    }
+
+    fn on_event(&mut self, event: Event) {
+        let is_played = self.is_played();
+        event.button(|args| {
+            on_hat_press(args, HatState::Down, || {
+                self.menu.move_cursor_down();
+            });
+
+            on_hat_press(args, HatState::Up, || {
+                self.menu.move_cursor_up();
+            });
+
+            on_button_press(args, 0, || {
+                let choice = if is_played {
+                    SceneEvent::GoToTitle
+                } else {
+                    self.menu.selected()
+                };
+                self.bus.lock().unwrap().send(choice).unwrap();
+            });
+        });
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::VecDeque,
+    f32::consts::{PI, TAU},
+    time::{Duration, Instant},
+};
+
+use legion::World;
+use rand::Rng;
+
+use crate::{
+    component::{Spatial, Sprite, SpriteKind, WrapAround},
+    resource::bounds::Bounds,
+};
+
+/// Frames the timings are taken over.
+const FRAMES: usize = 120;
+const SPEED: f32 = 80.0; // pixels per second
+
+/// Times frames while the arena is crowded with sprites, to see how well
+/// drawing copes.
+pub struct Benchmark {
+    /// Time between the starts of recent frames.
+    intervals: VecDeque<Duration>,
+    /// Time spent drawing recent frames.
+    draws: VecDeque<Duration>,
+    frame_start: Option<Instant>,
+    /// Entities drawn in the last frame.
+    pub drawn: usize,
+}
+
+impl Benchmark {
+    pub fn new() -> Self {
+        Self {
+            intervals: VecDeque::with_capacity(FRAMES),
+            draws: VecDeque::with_capacity(FRAMES),
+            frame_start: None,
+            drawn: 0,
+        }
+    }
+
+    pub fn start_frame(&mut self) {
+        let now = Instant::now();
+        if let Some(start) = self.frame_start {
+            record(&mut self.intervals, now - start);
+        }
+        self.frame_start = Some(now);
+    }
+
+    pub fn end_frame(&mut self) {
+        if let Some(start) = self.frame_start {
+            record(&mut self.draws, start.elapsed());
+        }
+    }
+
+    /// Lines for the HUD describing recent frames.
+    pub fn report(&self) -> Vec<String> {
+        let (interval, worst) = summarize(&self.intervals);
+        let (draw, _) = summarize(&self.draws);
+        vec![
+            format!("{} entities", self.drawn),
+            format!(
+                "Frame {:.2} ms, worst {:.2} ms",
+                millis(interval),
+                millis(worst)
+            ),
+            format!("Draw {:.2} ms", millis(draw)),
+        ]
+    }
+}
+
+/// Fills the arena with sprites drifting about, which take no part in the
+/// game.
+pub fn scatter(world: &mut World, bounds: &Bounds, count: usize) {
+    let mut rng = rand::thread_rng();
+    let kinds = [SpriteKind::Asteroid, SpriteKind::Bullet, SpriteKind::Player];
+    world.extend((0..count).map(|_| {
+        let angle = rng.gen_range(0.0..TAU);
+        let spatial = Spatial {
+            x: rng.gen_range(bounds.inner.p0.x..bounds.inner.p1.x),
+            y: rng.gen_range(bounds.inner.p0.y..bounds.inner.p1.y),
+            dx: angle.cos() * SPEED,
+            dy: angle.sin() * SPEED,
+            angle_o: angle,
+            spin: rng.gen_range(-PI..PI),
+            wrap: WrapAround::Wrap,
+        };
+        let size = rng.gen_range(8..32);
+        let sprite = Sprite {
+            width: size,
+            height: size,
+            kind: kinds[rng.gen_range(0..kinds.len())],
+        };
+        (spatial, sprite)
+    }));
+}
+
+fn record(times: &mut VecDeque<Duration>, time: Duration) {
+    if times.len() == FRAMES {
+        times.pop_front();
+    }
+    times.push_back(time);
+}
+
+/// The mean and the longest of the times.
+fn summarize(times: &VecDeque<Duration>) -> (Duration, Duration) {
+    if times.is_empty() {
+        return (Duration::ZERO, Duration::ZERO);
+    }
+    let total: Duration = times.iter().sum();
+    let worst = times.iter().max().copied().unwrap_or_default();
+    (total / times.len() as u32, worst)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
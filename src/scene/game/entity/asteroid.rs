@@ -1,36 +1,144 @@
-use std::ops::RangeInclusive;
+use std::{
+    f32::consts::{PI, TAU},
+    ops::RangeInclusive,
+};
 
-use rand::{prelude::ThreadRng, Rng};
+use rand::Rng;
 
 use crate::{
-    component::{Asteroid, HitMask, Spatial, Sprite, SpriteKind, WrapAround},
+    component::{
+        Asteroid, AsteroidSize, Body, Damage, Faction, Health, HitMask, ScoreValue, Spatial,
+        Sprite, SpriteKind, WrapAround,
+    },
     resource::bounds::Bounds,
 };
 
 const SPEED_RANGE: RangeInclusive<f32> = -100.0..=100.0; // pixels per second
+const SPIN_RANGE: RangeInclusive<f32> = -PI..=PI; // radians per second
+/// How fast fragments fly apart from one another, in pixels per second.
+const FRAGMENT_SPEED: f32 = 60.0;
 
 /// The "component signature" or "archetype" of an asteroid entity.
-pub type Archetype = (Spatial, Sprite, HitMask, Asteroid);
+pub type Archetype = (
+    Spatial,
+    Sprite,
+    HitMask,
+    Body,
+    Health,
+    Damage,
+    Faction,
+    ScoreValue,
+    Asteroid,
+);
 
-pub fn new<'a>(bounds: &'a Bounds) -> (Spatial, Sprite, HitMask, Asteroid) {
-    let mut rng = rand::thread_rng();
-    let (x, y) = coords_on_edge(&bounds, &mut rng);
-    (
+/// An asteroid entering at the edge of the arena, at a random velocity scaled
+/// by the given speed.
+pub fn new(bounds: &Bounds, speed: f32, rng: &mut impl Rng) -> Archetype {
+    let (x, y) = coords_on_edge(bounds, rng);
+    let size = match rng.gen_range(0..3) {
+        0 => AsteroidSize::Small,
+        1 => AsteroidSize::Medium,
+        _ => AsteroidSize::Large,
+    };
+    with_size(
         Spatial {
             x,
             y,
-            dx: rng.gen_range(SPEED_RANGE),
-            dy: rng.gen_range(SPEED_RANGE),
-            angle_o: rng.gen_range(0.0..360.0),
+            dx: rng.gen_range(SPEED_RANGE) * speed,
+            dy: rng.gen_range(SPEED_RANGE) * speed,
+            angle_o: rng.gen_range(0.0..TAU),
+            spin: rng.gen_range(SPIN_RANGE),
+            wrap: WrapAround::Wrap,
+        },
+        size,
+    )
+}
+
+/// Breaks a destroyed asteroid into two smaller asteroids, if it is large
+/// enough to break apart. The fragments fly apart from one another and carry
+/// away the linear momentum they had due to the parent's rotation.
+pub fn fragments(parent: &Spatial, size: AsteroidSize, rng: &mut impl Rng) -> Vec<Archetype> {
+    let size = match size.fragment() {
+        Some(size) => size,
+        None => return Vec::new(),
+    };
+
+    let axis = rng.gen_range(0.0..TAU);
+    let offset = size.radius();
+
+    [axis, axis + PI]
+        .iter()
+        .map(|angle| {
+            let (nx, ny) = (angle.cos(), angle.sin());
+            // Tangential velocity of a point at this offset on the rotating
+            // parent, i.e. spin × r.
+            let (tx, ty) = (-ny * parent.spin * offset, nx * parent.spin * offset);
+            with_size(
+                Spatial {
+                    x: parent.x + nx * offset,
+                    y: parent.y + ny * offset,
+                    dx: parent.dx + tx + nx * FRAGMENT_SPEED,
+                    dy: parent.dy + ty + ny * FRAGMENT_SPEED,
+                    angle_o: parent.angle_o,
+                    spin: parent.spin,
+                    wrap: parent.wrap,
+                },
+                size,
+            )
+        })
+        .collect()
+}
+
+/// Breaks a single asteroid of the given size off of the surface of a larger
+/// body, flinging it outward.
+pub fn shed(
+    parent: &Spatial,
+    parent_radius: f32,
+    size: AsteroidSize,
+    rng: &mut impl Rng,
+) -> Archetype {
+    let angle = rng.gen_range(0.0..TAU);
+    let (nx, ny) = (angle.cos(), angle.sin());
+    let offset = parent_radius + size.radius();
+    with_size(
+        Spatial {
+            x: parent.x + nx * offset,
+            y: parent.y + ny * offset,
+            dx: parent.dx + nx * FRAGMENT_SPEED * 2.0,
+            dy: parent.dy + ny * FRAGMENT_SPEED * 2.0,
+            angle_o: angle,
+            spin: rng.gen_range(SPIN_RANGE),
             wrap: WrapAround::Wrap,
         },
-        Sprite::default(SpriteKind::Asteroid),
-        HitMask::Circle { radius: 32.0 },
-        Asteroid,
+        size,
+    )
+}
+
+fn with_size(spatial: Spatial, size: AsteroidSize) -> Archetype {
+    let radius = size.radius();
+    // Shapes are taken from where the asteroid starts out rather than from the
+    // game's random numbers, which would otherwise change how it plays out.
+    let shape = spatial.x.to_bits() ^ spatial.y.to_bits().rotate_left(8) ^ spatial.dx.to_bits();
+    (
+        spatial,
+        Sprite {
+            width: (radius * 2.0) as u32,
+            height: (radius * 2.0) as u32,
+            kind: SpriteKind::Asteroid,
+        },
+        HitMask::Circle { radius },
+        Body {
+            mass: radius * radius,
+        },
+        Health::new(size.hp()),
+        Damage { amount: 1.0 },
+        Faction::Hostile,
+        ScoreValue(size.value()),
+        Asteroid { size, shape },
     )
 }
 
-fn coords_on_edge(bounds: &Bounds, rng: &mut ThreadRng) -> (f32, f32) {
+pub fn coords_on_edge(bounds: &Bounds, rng: &mut impl Rng) -> (f32, f32) {
     let mut x = bounds.outer.p0.x;
     let mut y = bounds.outer.p0.y;
     if rng.gen::<f32>() < 0.5 {
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::{
+    component::{
+        Armor, Body, Boss, BossKind, Damage, Faction, Health, HitMask, ScoreValue, Spatial, Sprite,
+        SpriteKind, WrapAround,
+    },
+    resource::{bounds::Bounds, clock::Instant},
+};
+
+pub const ARMORED_ASTEROID_RADIUS: f32 = 96.0;
+const ARMORED_ASTEROID_HP: f32 = 40.0;
+const ARMORED_ASTEROID_VALUE: u32 = 50;
+const ENTRY_SPEED: f32 = 40.0; // pixels per second
+
+pub type Archetype = (
+    Spatial,
+    Sprite,
+    HitMask,
+    Body,
+    Health,
+    Damage,
+    Faction,
+    ScoreValue,
+    Armor,
+    Boss,
+);
+
+/// A giant armored asteroid which drifts in from the edge of the arena toward
+/// its center.
+pub fn armored_asteroid(bounds: &Bounds, rng: &mut impl Rng, now: Instant) -> Archetype {
+    let (x, y) = super::asteroid::coords_on_edge(bounds, rng);
+    let (cx, cy) = (bounds.inner.width / 2.0, bounds.inner.height / 2.0);
+    let heading = (cy - y).atan2(cx - x);
+    let radius = ARMORED_ASTEROID_RADIUS;
+    (
+        Spatial {
+            x,
+            y,
+            dx: heading.cos() * ENTRY_SPEED,
+            dy: heading.sin() * ENTRY_SPEED,
+            angle_o: 0.0,
+            spin: 0.3,
+            wrap: WrapAround::Wrap,
+        },
+        Sprite {
+            width: (radius * 2.0) as u32,
+            height: (radius * 2.0) as u32,
+            kind: SpriteKind::Boss,
+        },
+        HitMask::Circle { radius },
+        Body {
+            mass: radius * radius * 4.0,
+        },
+        Health::new(ARMORED_ASTEROID_HP),
+        Damage { amount: 1.0 },
+        Faction::Hostile,
+        ScoreValue(ARMORED_ASTEROID_VALUE),
+        Armor { reduction: 0.5 },
+        Boss {
+            kind: BossKind::ArmoredAsteroid,
+            phase: 1,
+            next_shed: now + Duration::from_secs(3),
+        },
+    )
+}
@@ -1,10 +1,28 @@
-use crate::component::{Bullet, HitMask, Spatial, Sprite, SpriteKind};
+use crate::component::{Bullet, Damage, Faction, Health, HitMask, Spatial, Sprite, SpriteKind};
 
-pub fn new(spatial: Spatial) -> (Spatial, Sprite, HitMask, Bullet) {
+pub type Archetype = (Spatial, Sprite, HitMask, Health, Damage, Faction, Bullet);
+
+/// A projectile fired by the given faction. Bullets with a radius are drawn to
+/// match it.
+pub fn new(spatial: Spatial, faction: Faction, damage: f32, radius: Option<f32>) -> Archetype {
+    let (sprite, mask) = match radius {
+        Some(radius) => (
+            Sprite {
+                width: (radius * 4.0) as u32,
+                height: (radius * 4.0) as u32,
+                kind: SpriteKind::Bullet,
+            },
+            HitMask::Circle { radius },
+        ),
+        None => (Sprite::default(SpriteKind::Bullet), HitMask::Point),
+    };
     (
         spatial,
-        Sprite::default(SpriteKind::Bullet),
-        HitMask::Point,
+        sprite,
+        mask,
+        Health::new(1.0),
+        Damage { amount: damage },
+        faction,
         Bullet,
     )
 }
@@ -0,0 +1,137 @@
+use std::{f32::consts::PI, time::Duration};
+
+use rand::Rng;
+
+use crate::{
+    component::{Emitter, Particle, Spatial, WrapAround},
+    resource::clock::Instant,
+};
+
+const FIRE: [f32; 4] = [1.0, 0.8, 0.3, 1.0];
+const SMOKE: [f32; 4] = [0.4, 0.4, 0.4, 0.0];
+const EXHAUST: [f32; 4] = [0.4, 0.7, 1.0, 0.9];
+const SPARK: [f32; 4] = [1.0, 1.0, 0.8, 1.0];
+
+/// Debris from a destroyed asteroid, scaled by the size of the asteroid.
+pub fn explosion(origin: &Spatial, radius: f32) -> (Spatial, Emitter) {
+    burst(
+        origin,
+        Emitter {
+            burst: Some((radius * 1.5) as u32),
+            speed: (20.0, radius * 4.0),
+            lifetime: (Duration::from_millis(300), Duration::from_millis(900)),
+            color: (FIRE, SMOKE),
+            size: (4.0, 1.0),
+            ..omnidirectional()
+        },
+    )
+}
+
+/// Sparks thrown off where a projectile strikes its target.
+pub fn impact(x: f32, y: f32) -> (Spatial, Emitter) {
+    burst(
+        &at(x, y),
+        Emitter {
+            burst: Some(8),
+            speed: (60.0, 240.0),
+            lifetime: (Duration::from_millis(100), Duration::from_millis(250)),
+            color: (SPARK, [1.0, 0.5, 0.0, 0.0]),
+            size: (2.0, 1.0),
+            ..omnidirectional()
+        },
+    )
+}
+
+/// A large, slow-burning explosion marking the destruction of a ship.
+pub fn ship_explosion(origin: &Spatial) -> (Spatial, Emitter) {
+    burst(
+        origin,
+        Emitter {
+            burst: Some(200),
+            speed: (10.0, 300.0),
+            lifetime: (Duration::from_millis(500), Duration::from_millis(1500)),
+            color: ([1.0, 1.0, 1.0, 1.0], [1.0, 0.2, 0.0, 0.0]),
+            size: (5.0, 1.0),
+            ..omnidirectional()
+        },
+    )
+}
+
+/// Exhaust trailing behind a ship. The owner is responsible for activating the
+/// emitter and pointing it away from the direction of thrust.
+pub fn thrust() -> Emitter {
+    Emitter {
+        active: false,
+        rate: 120.0,
+        burst: None,
+        direction: 0.0,
+        spread: PI / 12.0,
+        speed: (80.0, 160.0),
+        lifetime: (Duration::from_millis(150), Duration::from_millis(350)),
+        color: (EXHAUST, [0.1, 0.1, 0.6, 0.0]),
+        size: (3.0, 1.0),
+        pending: 0.0,
+    }
+}
+
+/// Creates a single particle from the given emitter.
+pub fn particle(
+    emitter: &Emitter,
+    origin: &Spatial,
+    now: Instant,
+    rng: &mut impl Rng,
+) -> (Spatial, Particle) {
+    let angle = emitter.direction + rng.gen_range(-emitter.spread..=emitter.spread);
+    let speed = rng.gen_range(emitter.speed.0..=emitter.speed.1);
+    let lifetime = rng.gen_range(emitter.lifetime.0..=emitter.lifetime.1);
+    (
+        Spatial {
+            x: origin.x,
+            y: origin.y,
+            dx: origin.dx + angle.cos() * speed,
+            dy: origin.dy + angle.sin() * speed,
+            angle_o: 0.0,
+            spin: 0.0,
+            wrap: WrapAround::Destroy,
+        },
+        Particle {
+            born: now,
+            lifetime,
+            color: emitter.color,
+            size: emitter.size,
+        },
+    )
+}
+
+fn burst(origin: &Spatial, emitter: Emitter) -> (Spatial, Emitter) {
+    (
+        Spatial {
+            angle_o: 0.0,
+            spin: 0.0,
+            wrap: WrapAround::Destroy,
+            ..*origin
+        },
+        emitter,
+    )
+}
+
+fn omnidirectional() -> Emitter {
+    Emitter {
+        active: true,
+        direction: 0.0,
+        spread: PI,
+        ..thrust()
+    }
+}
+
+fn at(x: f32, y: f32) -> Spatial {
+    Spatial {
+        x,
+        y,
+        dx: 0.0,
+        dy: 0.0,
+        angle_o: 0.0,
+        spin: 0.0,
+        wrap: WrapAround::Destroy,
+    }
+}
@@ -0,0 +1,70 @@
+use rand::Rng;
+
+use crate::{
+    component::{Hazard, Spatial, WrapAround},
+    resource::{bounds::Bounds, tuning::HazardLayout, wave::Wave},
+};
+
+/// How close a hazard may appear to the player, beyond its own radius.
+const CLEARANCE: f32 = 120.0;
+
+pub type Archetype = (Spatial, Hazard);
+
+/// The hazards for the given wave, as the layout has them, placed away from
+/// the player. Boss waves are clear.
+pub fn for_wave(
+    wave: &Wave,
+    layout: &HazardLayout,
+    bounds: &Bounds,
+    player: Option<(f32, f32)>,
+    rng: &mut impl Rng,
+) -> Vec<Archetype> {
+    if wave.boss {
+        return Vec::new();
+    }
+
+    (0..layout.count(wave.number))
+        .filter_map(|_| {
+            let hazard = layout.pick(rng)?;
+            let (x, y) = placement(bounds, hazard.radius, player, rng);
+            Some(new(x, y, hazard))
+        })
+        .collect()
+}
+
+fn new(x: f32, y: f32, hazard: Hazard) -> Archetype {
+    (
+        Spatial {
+            x,
+            y,
+            dx: 0.0,
+            dy: 0.0,
+            angle_o: 0.0,
+            spin: 0.0,
+            wrap: WrapAround::Wrap,
+        },
+        hazard,
+    )
+}
+
+/// Picks a spot in the arena clear of the player. Gives up after a few tries
+/// in arenas too small to keep clear.
+fn placement(
+    bounds: &Bounds,
+    radius: f32,
+    player: Option<(f32, f32)>,
+    rng: &mut impl Rng,
+) -> (f32, f32) {
+    let mut spot = (0.0, 0.0);
+    for _ in 0..8 {
+        spot = (
+            rng.gen_range(bounds.inner.p0.x..bounds.inner.p1.x),
+            rng.gen_range(bounds.inner.p0.y..bounds.inner.p1.y),
+        );
+        match player {
+            Some((x, y)) if (spot.0 - x).hypot(spot.1 - y) < radius + CLEARANCE => continue,
+            _ => break,
+        }
+    }
+    spot
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use crate::{
+    component::{Damage, Emitter, Faction, Health, HitMask, Missile, Spatial},
+    resource::clock::Instant,
+};
+
+/// How long a missile steers before it burns out.
+const FUEL: Duration = Duration::from_millis(2500);
+const TURN_RATE: f32 = 3.0; // radians per second
+
+pub type Archetype = (Spatial, HitMask, Health, Damage, Faction, Emitter, Missile);
+
+pub fn new(spatial: Spatial, faction: Faction, damage: f32, speed: f32, now: Instant) -> Archetype {
+    (
+        spatial,
+        HitMask::Point,
+        Health::new(1.0),
+        Damage { amount: damage },
+        faction,
+        Emitter {
+            active: true,
+            rate: 60.0,
+            ..super::emitter::thrust()
+        },
+        Missile {
+            speed,
+            turn_rate: TURN_RATE,
+            fuel_until: now + FUEL,
+        },
+    )
+}
@@ -1,3 +1,8 @@
 pub mod asteroid;
+pub mod boss;
 pub mod bullet;
+pub mod emitter;
+pub mod hazard;
+pub mod missile;
 pub mod player;
+pub mod popup;
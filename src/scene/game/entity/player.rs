@@ -1,21 +1,49 @@
-use crate::{
-    component::{HitMask, Player, PlayerInput, Spatial, Sprite, SpriteKind, WrapAround},
-    resource::bounds::Bounds,
+use std::time::Duration;
+
+use crate::component::{
+    Emitter, Faction, Health, HitMask, Player, PlayerInput, Spatial, Sprite, SpriteKind, Weapon,
+    WeaponKind, WrapAround,
 };
 
-pub fn new(bounds: &Bounds) -> (Spatial, Sprite, HitMask, PlayerInput, Player) {
+const HULL_POINTS: f32 = 3.0;
+const IFRAMES: Duration = Duration::from_secs(2);
+
+/// The ship flown by the player in the given seat.
+pub fn new(
+    x: f32,
+    y: f32,
+    seat: u8,
+) -> (
+    Spatial,
+    Sprite,
+    HitMask,
+    Health,
+    Faction,
+    Emitter,
+    Weapon,
+    PlayerInput,
+    Player,
+) {
     (
         Spatial {
-            x: bounds.inner.width / 2.0,
-            y: bounds.inner.height / 2.0,
+            x,
+            y,
             dx: 0.0,
             dy: 0.0,
             angle_o: 0.0,
+            spin: 0.0,
             wrap: WrapAround::Wrap,
         },
         Sprite::default(SpriteKind::Player),
         HitMask::Point,
-        PlayerInput,
+        Health::new(HULL_POINTS).with_iframes(IFRAMES),
+        Faction::Player(seat),
+        super::emitter::thrust(),
+        Weapon::new(WeaponKind::Single),
+        PlayerInput {
+            seat,
+            was_switching: false,
+        },
         Player,
     )
 }
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use crate::{
+    component::{ScorePopup, Spatial, WrapAround},
+    resource::clock::Instant,
+};
+
+pub const LIFETIME: Duration = Duration::from_millis(800);
+const RISE_SPEED: f32 = -40.0; // pixels per second
+
+pub fn new(x: f32, y: f32, points: u32, multiplier: u32, now: Instant) -> (Spatial, ScorePopup) {
+    (
+        Spatial {
+            x,
+            y,
+            dx: 0.0,
+            dy: RISE_SPEED,
+            angle_o: 0.0,
+            spin: 0.0,
+            wrap: WrapAround::Destroy,
+        },
+        ScorePopup {
+            points,
+            multiplier,
+            until: now + LIFETIME,
+        },
+    )
+}
@@ -1,69 +1,425 @@
+mod benchmark;
 mod entity;
+mod save;
+mod simulation;
 mod system;
+mod vector;
 
 use std::{
     rc::Rc,
-    sync::{mpsc::Sender, Arc, Mutex},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
     time::Duration,
 };
 
-use legion::{Resources, Schedule, World};
 use opengl_graphics::GlGraphics;
-use piston::{Event, RenderArgs, UpdateArgs};
+use piston::{ButtonEvent, ButtonState, Event, RenderArgs, UpdateArgs};
 
 use crate::{
-    component::{Spatial, Sprite},
+    atlas::SpriteBatch,
+    autopilot::Autopilot,
+    bot::{Bot, Observation},
+    component::{
+        Boss, Hazard, HazardKind, Health, Missile, Particle, PlayerInput, ScorePopup, Spatial,
+        Sprite, Weapon,
+    },
     controller::ControllerState,
-    resource::{bounds::Bounds, clock::Clock, score::Score, textures::Textures},
+    font::{Font, Layout},
+    netplay::{Outcome, Rollback, Session},
+    profile::{Bindings, Graphics, Profile},
+    replay::Replay,
+    resource::{
+        bounds::Bounds, camera::Camera, clock::Instant, game_mode::GameMode, scoring::Scoring,
+        textures::Textures, tuning::Tuning, wave::Wave,
+    },
+    spectate::{Broadcast, Broadcaster, Spectating},
 };
 
 use super::{scene_event::SceneEvent, Scene};
 
+use benchmark::Benchmark;
+use vector::Outlines;
+
+pub use simulation::{Simulation, Snapshot};
+
+/// Tells the players apart in versus.
+const SEAT_COLORS: [[f32; 4]; 2] = [[1.0, 1.0, 1.0, 1.0], [1.0, 0.45, 0.3, 1.0]];
+
 pub struct GameScene {
     textures: Rc<Textures>,
-    world: World,
-    resources: Resources,
-    update: Schedule,
+    font: Rc<Font>,
+    sim: Simulation,
+    /// Keeps the game in step with another player's, in versus.
+    netplay: Option<Rollback>,
+    /// Plays out a game broadcast from another machine, when watching one.
+    spectating: Option<Spectating>,
+    /// Sends the game to anyone watching.
+    broadcast: Option<Broadcast>,
+    /// Flies the ship in place of the local controller.
+    bot: Option<Bot>,
+    /// Fly the ships in their seats in place of anyone else, whether as an
+    /// opponent, an assist, or a demo.
+    autopilots: Vec<Autopilot>,
+    /// The simulation's own events, when they are not for the scene bus.
+    events: Option<Receiver<SceneEvent>>,
+    /// Whether the game is only being shown off.
+    is_demo: bool,
+    /// Times frames, when the game is only there to see how fast it draws.
+    benchmark: Option<Benchmark>,
+    bus: Arc<Mutex<Sender<SceneEvent>>>,
     is_paused: bool,
-    clock: Clock,
     controller: ControllerState,
+    bindings: Bindings,
+    graphics: Graphics,
+    /// Outlines traced for the vector graphics.
+    outlines: Outlines,
+    mode: GameMode,
+    /// Name of the profile playing, recorded in saves.
+    profile: String,
 }
 
 impl GameScene {
+    /// Starts a new game. In versus, the other seats are flown by autopilots.
     pub fn new(
         textures: Rc<Textures>,
+        font: Rc<Font>,
         bounds: Bounds,
+        profile: &Profile,
+        mode: GameMode,
+        seed: u64,
         bus: Arc<Mutex<Sender<SceneEvent>>>,
     ) -> Self {
-        let mut world = World::default();
+        if mode.seats() == 1 {
+            let sim = Simulation::new(bounds, profile.settings, mode, seed, Arc::clone(&bus));
+            return Self::with_simulation(textures, font, sim, profile, bus);
+        }
+
+        // A game of versus ends with a winner, not a score, so the scene
+        // decides how it went instead of the scene bus.
+        let (sender, events) = mpsc::channel();
+        let sim = Simulation::new(
+            bounds,
+            profile.settings,
+            mode,
+            seed,
+            Arc::new(Mutex::new(sender)),
+        );
+        Self {
+            autopilots: (1..mode.seats()).map(Autopilot::new).collect(),
+            events: Some(events),
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        }
+    }
 
-        world.push(entity::asteroid::new(&bounds));
-        world.push(entity::asteroid::new(&bounds));
-        world.push(entity::asteroid::new(&bounds));
-        world.push(entity::asteroid::new(&bounds));
-        world.push(entity::asteroid::new(&bounds));
-        world.push(entity::player::new(&bounds));
+    /// Starts a game of versus against the player at the other end of the
+    /// session. Both players play by the default settings, so that their games
+    /// stay the same.
+    pub fn versus(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        bounds: Bounds,
+        profile: &Profile,
+        session: Session,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
+        // The simulation's own events are only final once the other player's
+        // inputs have arrived, so they go to the rollback instead of the
+        // scene bus.
+        let (sender, events) = mpsc::channel();
+        let sim = Simulation::new(
+            bounds,
+            Tuning::new(),
+            GameMode::Versus,
+            session.seed,
+            Arc::new(Mutex::new(sender)),
+        );
+
+        Self {
+            netplay: Some(Rollback::new(session, events)),
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        }
+    }
+
+    /// Watches a game being played on another machine.
+    pub fn spectate(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        profile: &Profile,
+        mut spectating: Spectating,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
+        let sim = spectating.simulation();
+        Self {
+            spectating: Some(spectating),
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        }
+    }
 
-        let mut resources = Resources::default();
-        resources.insert(bounds);
-        resources.insert(bus);
-        resources.insert(Score::new());
+    /// Shows off the game, flown by an autopilot, until any button is
+    /// pressed.
+    pub fn demo(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        bounds: Bounds,
+        profile: &Profile,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
+        // The demo's score doesn't count for anything, so the end of the game
+        // is kept from the scene bus.
+        let (sender, events) = mpsc::channel();
+        let sim = Simulation::new(
+            bounds,
+            Tuning::new(),
+            GameMode::Classic,
+            rand::random(),
+            Arc::new(Mutex::new(sender)),
+        );
+        Self {
+            autopilots: vec![Autopilot::new(0)],
+            events: Some(events),
+            is_demo: true,
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        }
+    }
 
-        let clock = Clock::new();
+    /// A demo crowded with the given number of sprites, which shows how long
+    /// each frame takes to draw.
+    pub fn benchmark(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        bounds: Bounds,
+        profile: &Profile,
+        sprites: usize,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
+        let (sender, events) = mpsc::channel();
+        let mut sim = Simulation::new(
+            bounds,
+            Tuning::new(),
+            GameMode::Zen,
+            rand::random(),
+            Arc::new(Mutex::new(sender)),
+        );
+        let arena = *sim.resources.get::<Bounds>().unwrap();
+        benchmark::scatter(&mut sim.world, &arena, sprites);
+        Self {
+            autopilots: vec![Autopilot::new(0)],
+            events: Some(events),
+            is_demo: true,
+            benchmark: Some(Benchmark::new()),
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        }
+    }
 
+    /// A scene for the given game, played at this machine by the local
+    /// controller.
+    fn with_simulation(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        sim: Simulation,
+        profile: &Profile,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
         Self {
             textures,
-            world,
-            resources,
-            update: update_schedule(&clock),
+            font,
+            mode: sim.mode(),
+            sim,
+            netplay: None,
+            spectating: None,
+            broadcast: None,
+            bot: None,
+            autopilots: Vec::new(),
+            events: None,
+            is_demo: false,
+            benchmark: None,
+            bus,
             is_paused: false,
-            clock,
             controller: ControllerState::new(),
+            bindings: profile.bindings,
+            graphics: profile.graphics,
+            outlines: Outlines::default(),
+            profile: profile.name.clone(),
+        }
+    }
+
+    /// Lets spectators watch the game, if anyone is broadcasting.
+    pub fn with_broadcast(self, broadcaster: Option<&Broadcaster>) -> Self {
+        Self {
+            broadcast: broadcaster.map(Broadcaster::broadcast),
+            ..self
+        }
+    }
+
+    /// Hands the ship over to a bot. The game is kept off the records, as if
+    /// the player had been assisted.
+    pub fn with_bot(mut self, bot: Bot) -> Self {
+        self.sim.replay.assisted = true;
+        Self {
+            bot: Some(bot),
+            ..self
+        }
+    }
+
+    /// Whether the game is played at this machine alone.
+    fn is_local(&self) -> bool {
+        self.netplay.is_none() && self.spectating.is_none() && !self.is_demo
+    }
+
+    /// Hands the local player's ship over to an autopilot, or takes it back.
+    fn toggle_assist(&mut self) {
+        if !self.is_local() {
+            return;
+        }
+        let seat = self.seat();
+        match self
+            .autopilots
+            .iter()
+            .position(|pilot| pilot.seat() == seat)
+        {
+            Some(i) => {
+                self.autopilots.remove(i);
+            }
+            None => {
+                self.autopilots.push(Autopilot::new(seat));
+                self.sim.replay.assisted = true;
+            }
         }
     }
 
+    fn is_assisted(&self) -> bool {
+        self.is_local()
+            && self
+                .autopilots
+                .iter()
+                .any(|pilot| pilot.seat() == self.seat())
+    }
+
+    /// The controller in each seat, for a game played at this machine.
+    fn controllers(&mut self) -> Vec<ControllerState> {
+        let observation = if self.bot.is_some() || !self.autopilots.is_empty() {
+            Some(Observation::of(&self.sim))
+        } else {
+            None
+        };
+
+        let mut controllers = Vec::new();
+        for seat in 0..self.mode.seats() {
+            let autopilot = self.autopilots.iter().find(|pilot| pilot.seat() == seat);
+            let controller = match (autopilot, &mut self.bot, &observation) {
+                (Some(autopilot), _, Some(observation)) => autopilot.control(observation),
+                (None, Some(bot), Some(observation)) if seat == 0 => {
+                    match bot.control(observation) {
+                        Ok(controller) => controller,
+                        Err(e) => {
+                            log::warn!("Bot left, handing back control: {}", e);
+                            self.bot = None;
+                            self.controller
+                        }
+                    }
+                }
+                _ => self.controller,
+            };
+            controllers.push(controller);
+        }
+        controllers
+    }
+
+    /// Watches for the end of a game whose events are kept from the scene bus.
+    fn check_game_over(&mut self) {
+        use legion::IntoQuery;
+
+        let is_over = self.events.as_ref().is_some_and(|events| {
+            events
+                .try_iter()
+                .any(|event| matches!(event, SceneEvent::GameOver { .. }))
+        });
+        if !is_over {
+            return;
+        }
+
+        let event = if self.is_demo {
+            SceneEvent::GoToTitle
+        } else {
+            let survivors: Vec<_> = <&PlayerInput>::query()
+                .iter(&self.sim.world)
+                .map(|input| input.seat)
+                .collect();
+            SceneEvent::VersusOver {
+                outcome: Outcome::of(self.seat(), &survivors),
+            }
+        };
+        self.bus.lock().unwrap().send(event).unwrap();
+    }
+
+    /// The seat of the player at this machine.
+    fn seat(&self) -> u8 {
+        self.netplay.as_ref().map_or(0, Rollback::seat)
+    }
+
     fn toggle_pause(&mut self) {
-        self.is_paused = !self.is_paused;
+        // Someone else's game can't be paused from here.
+        if self.is_local() {
+            self.is_paused = !self.is_paused;
+        }
+    }
+
+    /// Saves the game and returns to the title screen.
+    fn save_and_quit(&mut self) {
+        if !self.can_suspend() {
+            return;
+        }
+
+        if let Err(e) = self.suspend() {
+            log::error!("Could not save game: {}", e);
+            return;
+        }
+
+        self.bus
+            .lock()
+            .unwrap()
+            .send(SceneEvent::GoToTitle)
+            .unwrap();
+    }
+
+    /// Ends the game early. Only modes without any other end condition may be
+    /// finished this way.
+    fn finish(&mut self) {
+        if !self.mode.can_finish() || !self.is_local() {
+            return;
+        }
+
+        self.bus
+            .lock()
+            .unwrap()
+            .send(SceneEvent::GameOver {
+                mode: self.mode,
+                current_score: self.sim.score(),
+                breakdown: self.sim.resources.get::<Scoring>().unwrap().breakdown,
+            })
+            .unwrap();
+    }
+
+    /// Mode-specific status line for the HUD, if any.
+    fn status(&self) -> Option<String> {
+        let elapsed = self.sim.clock.elapsed();
+        match self.mode {
+            _ if self.spectating.is_some() => Some(format!("Watching {}", self.mode.name())),
+            _ if self.benchmark.is_some() => Some("Benchmark: press any button".to_owned()),
+            _ if self.is_demo => Some("Demo: press any button".to_owned()),
+            GameMode::Classic => None,
+            GameMode::TimeAttack => {
+                let limit = self.mode.time_limit().unwrap();
+                Some(format_time(limit - elapsed.min(limit)))
+            }
+            GameMode::Survival => Some(format!("Survived {}", format_time(elapsed))),
+            GameMode::Zen => Some("Zen: press back to finish".to_owned()),
+            GameMode::Daily => Some(self.mode.name().to_owned()),
+            GameMode::Versus if self.netplay.is_none() => Some("Versus CPU".to_owned()),
+            GameMode::Versus => Some(format!("Versus: you are player {}", self.seat() + 1)),
+        }
     }
 }
 
@@ -73,40 +429,410 @@ impl Scene for GameScene {
             return;
         }
 
-        self.clock.update(Duration::from_secs_f64(args.dt));
-        self.resources.insert(self.clock);
-        self.resources.insert(self.controller);
-
-        self.update.execute(&mut self.world, &mut self.resources);
+        let dt = Duration::from_secs_f64(args.dt);
+        if let Some(netplay) = &mut self.netplay {
+            if let Some(outcome) = netplay.advance(&mut self.sim, dt, self.controller) {
+                self.bus
+                    .lock()
+                    .unwrap()
+                    .send(SceneEvent::VersusOver { outcome })
+                    .unwrap();
+            }
+        } else if let Some(spectating) = &mut self.spectating {
+            if let Some(ending) = spectating.advance(&mut self.sim) {
+                self.bus
+                    .lock()
+                    .unwrap()
+                    .send(SceneEvent::SpectatingOver { ending })
+                    .unwrap();
+            }
+        } else {
+            let controllers = self.controllers();
+            self.sim.step(dt, &controllers);
+            if let Some(broadcast) = &mut self.broadcast {
+                broadcast.update(&self.sim.replay);
+            }
+            self.check_game_over();
+        }
     }
 
     fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
-        use graphics::{clear, image, Transformed};
+        use graphics::{clear, color, ellipse, line, rectangle, Transformed};
         use legion::IntoQuery;
 
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.start_frame();
+        }
+        let now = self.sim.clock.now;
+        let score = self.sim.score();
+        let multiplier = self.sim.resources.get::<Scoring>().unwrap().multiplier(now);
+        let [_, _, width, height] = args.viewport().rect;
+        let (width, height) = (width as f64, height as f64);
+
+        let mut hud = vec![self.font.create_text(&score.to_string()).unwrap()];
+        if multiplier > 1 {
+            hud.push(self.font.create_text(&format!("x{}", multiplier)).unwrap());
+        }
+        let hud_width = hud.iter().map(|x| x.width).fold(0.0, f64::max);
+        let mut status: Vec<_> = self
+            .status()
+            .iter()
+            .map(|status| self.font.create_text(status).unwrap())
+            .collect();
+        if self
+            .spectating
+            .as_ref()
+            .is_some_and(Spectating::is_buffering)
+        {
+            status.push(self.font.create_text("Buffering...").unwrap());
+        }
+        if self.is_assisted() {
+            status.push(self.font.create_text("Assist").unwrap());
+        }
+        if let Some(benchmark) = &self.benchmark {
+            status.extend(
+                benchmark
+                    .report()
+                    .iter()
+                    .map(|line| self.font.create_text(line).unwrap()),
+            );
+        }
+        if self.is_paused {
+            let paused = if self.can_suspend() {
+                "Paused: press back to save and quit"
+            } else {
+                "Paused"
+            };
+            status.push(self.font.create_text(paused).unwrap());
+        }
+        if self.mode.has_bosses() {
+            let wave = self.sim.resources.get::<Wave>().unwrap();
+            status.push(
+                self.font
+                    .create_text(&format!("Wave {}", wave.number))
+                    .unwrap(),
+            );
+        }
+        let camera = *self.sim.resources.get::<Camera>().unwrap();
+        let seat = self.seat();
+        let weapon = <(&Weapon, &PlayerInput)>::query()
+            .iter(&self.sim.world)
+            .find(|(_, input)| input.seat == seat)
+            .map(|(weapon, _)| {
+                (
+                    *weapon,
+                    vec![self.font.create_text(weapon.kind.name()).unwrap()],
+                )
+            });
+        let boss = <(&Health, &Boss)>::query()
+            .iter(&self.sim.world)
+            .next()
+            .map(|(health, boss)| {
+                (
+                    health.hp / health.max,
+                    vec![self.font.create_text(boss.kind.name()).unwrap()],
+                )
+            });
+
+        let mut drawn = 0;
         gl.draw(args.viewport(), |c, g| {
             clear([0.0; 4], g);
-            <(&Spatial, &Sprite)>::query().for_each(&self.world, |(spatial, sprite)| {
-                let texture = self.textures.get(&sprite.kind).unwrap();
 
-                let transform = c
-                    .trans(spatial.x as f64, spatial.y as f64)
-                    .rot_rad(spatial.angle_o as f64)
-                    .trans(sprite.width as f64 / -2.0, sprite.height as f64 / -2.0)
-                    .transform;
+            // The world is drawn through the camera; the HUD is drawn directly
+            // to the screen.
+            let view = camera.view(&c);
+
+            <(&Spatial, &Particle)>::query().for_each(&self.sim.world, |(spatial, particle)| {
+                let size = particle.size_at(now) as f64;
+                rectangle(
+                    particle.color_at(now),
+                    rectangle::centered_square(spatial.x as f64, spatial.y as f64, size / 2.0),
+                    view.transform,
+                    g,
+                );
+            });
+
+            // Wells and repulsors are drawn beneath everything else; nebulae are
+            // drawn over the top, hiding whatever is inside them.
+            <(&Spatial, &Hazard)>::query().for_each(&self.sim.world, |(spatial, hazard)| {
+                let color = match hazard.kind {
+                    HazardKind::GravityWell { .. } => [0.4, 0.1, 0.6, 0.25],
+                    HazardKind::Repulsor { .. } => [0.1, 0.6, 0.7, 0.2],
+                    HazardKind::Nebula { .. } => return,
+                };
+                let circle =
+                    ellipse::circle(spatial.x as f64, spatial.y as f64, hazard.radius as f64);
+                ellipse(color, circle, view.transform, g);
+                ellipse(
+                    [0.0, 0.0, 0.0, 1.0],
+                    ellipse::circle(spatial.x as f64, spatial.y as f64, 16.0),
+                    view.transform,
+                    g,
+                );
+            });
+
+            // A halo in each player's color, in versus.
+            if self.mode.seats() > 1 {
+                <(&Spatial, &PlayerInput)>::query().for_each(
+                    &self.sim.world,
+                    |(spatial, input)| {
+                        let [r, g_, b, _] = SEAT_COLORS[input.seat as usize % SEAT_COLORS.len()];
+                        ellipse(
+                            [r, g_, b, 0.3],
+                            ellipse::circle(spatial.x as f64, spatial.y as f64, 24.0),
+                            view.transform,
+                            g,
+                        );
+                    },
+                );
+            }
+
+            match self.graphics {
+                Graphics::Sprites => {
+                    let mut batch = SpriteBatch::new(&*self.textures);
+                    <(&Spatial, &Sprite, Option<&Health>)>::query().for_each(
+                        &self.sim.world,
+                        |(spatial, sprite, health)| {
+                            // Invulnerable entities blink.
+                            if let Some(health) = health {
+                                if health.is_invulnerable(now) && is_blink(health, now) {
+                                    return;
+                                }
+                            }
+
+                            let (width, height) = (sprite.width as f64, sprite.height as f64);
+                            let transform = view
+                                .trans(spatial.x as f64, spatial.y as f64)
+                                .rot_rad(spatial.angle_o as f64)
+                                .transform;
+                            batch.push(
+                                &sprite.kind,
+                                [width / -2.0, height / -2.0, width, height],
+                                color::WHITE,
+                                transform,
+                            );
+                        },
+                    );
+                    batch.draw(&view.draw_state, g);
+                    drawn = batch.len();
+                }
+                Graphics::Vector => {
+                    drawn = vector::draw(&self.sim.world, now, &mut self.outlines, &view, g)
+                }
+            }
+
+            <(&Spatial, &Missile)>::query().for_each(&self.sim.world, |(spatial, _)| {
+                let (x, y) = (spatial.x as f64, spatial.y as f64);
+                let (sin, cos) = (spatial.angle_o as f64).sin_cos();
+                line(
+                    [1.0, 0.6, 0.2, 1.0],
+                    2.0,
+                    [x - cos * 8.0, y - sin * 8.0, x + cos * 8.0, y + sin * 8.0],
+                    view.transform,
+                    g,
+                );
+            });
+
+            // Laser beams: a wide glow around a bright core.
+            <(&Spatial, &Weapon)>::query().for_each(&self.sim.world, |(spatial, weapon)| {
+                if let Some(length) = weapon.beam {
+                    let (sin, cos) = (spatial.angle_o as f64).sin_cos();
+                    let muzzle = weapon.muzzle as f64;
+                    let end = muzzle + length as f64;
+                    let beam = [
+                        spatial.x as f64 + cos * muzzle,
+                        spatial.y as f64 + sin * muzzle,
+                        spatial.x as f64 + cos * end,
+                        spatial.y as f64 + sin * end,
+                    ];
+                    line([1.0, 0.1, 0.1, 0.4], 4.0, beam, view.transform, g);
+                    line([1.0, 0.9, 0.9, 1.0], 1.0, beam, view.transform, g);
+                }
+            });
 
-                image(texture, transform, g)
+            <(&Spatial, &Hazard)>::query().for_each(&self.sim.world, |(spatial, hazard)| {
+                if let HazardKind::Nebula { .. } = hazard.kind {
+                    let circle =
+                        ellipse::circle(spatial.x as f64, spatial.y as f64, hazard.radius as f64);
+                    ellipse([0.25, 0.15, 0.35, 0.95], circle, view.transform, g);
+                }
             });
-        })
+
+            <(&Spatial, &ScorePopup)>::query().for_each(&self.sim.world, |(spatial, popup)| {
+                let text = if popup.multiplier > 1 {
+                    format!("+{} x{}", popup.points, popup.multiplier)
+                } else {
+                    format!("+{}", popup.points)
+                };
+                let alpha = (popup.until - now.min(popup.until)).as_secs_f32()
+                    / entity::popup::LIFETIME.as_secs_f32();
+                self.font
+                    .render_lines(
+                        &vec![self.font.create_text(&text).unwrap()],
+                        Layout::Centered,
+                        (spatial.x as f64, spatial.y as f64),
+                        [1.0, 1.0, 0.0, alpha.min(1.0)],
+                        &view,
+                        g,
+                    )
+                    .unwrap();
+            });
+
+            // Remaining hull points, top-left. In versus, the other player's
+            // are shown top-right, beneath the score.
+            <(&Health, &PlayerInput)>::query().for_each(&self.sim.world, |(health, input)| {
+                let (x, y, step) = if input.seat == seat {
+                    (10.0, 10.0, 16.0)
+                } else {
+                    (width - 20.0, 60.0, -16.0)
+                };
+                for i in 0..(health.max.ceil() as u32) {
+                    let color = if (i as f32) < health.hp {
+                        SEAT_COLORS[input.seat as usize % SEAT_COLORS.len()]
+                    } else {
+                        color::grey(0.3)
+                    };
+                    rectangle(color, [x + i as f64 * step, y, 10.0, 10.0], c.transform, g);
+                }
+            });
+
+            // Weapon, heat and charge, beneath the hull points.
+            if let Some((weapon, name)) = &weapon {
+                let heat = if weapon.overheated {
+                    [1.0, 0.2, 0.1, 1.0]
+                } else {
+                    [1.0, 0.6, 0.1, 1.0]
+                };
+                rectangle(color::grey(0.3), [10.0, 26.0, 90.0, 4.0], c.transform, g);
+                rectangle(
+                    heat,
+                    [10.0, 26.0, 90.0 * weapon.heat as f64, 4.0],
+                    c.transform,
+                    g,
+                );
+                if weapon.charging_since.is_some() {
+                    rectangle(
+                        color::WHITE,
+                        [10.0, 32.0, 90.0 * weapon.charge(now) as f64, 4.0],
+                        c.transform,
+                        g,
+                    );
+                }
+                self.font
+                    .render_lines(
+                        name,
+                        Layout::LeftAlign,
+                        (10.0 + name[0].width / 2.0, 56.0),
+                        color::WHITE,
+                        &c,
+                        g,
+                    )
+                    .unwrap();
+            }
+
+            if !status.is_empty() {
+                self.font
+                    .render_lines(
+                        &status,
+                        Layout::Centered,
+                        (width / 2.0, 30.0),
+                        color::WHITE,
+                        &c,
+                        g,
+                    )
+                    .unwrap();
+            }
+
+            // Boss name and health, bottom center.
+            if let Some((fraction, name)) = &boss {
+                let bar = [width / 4.0, height - 40.0, width / 2.0, 12.0];
+                rectangle(color::grey(0.3), bar, c.transform, g);
+                rectangle(
+                    [0.8, 0.1, 0.1, 1.0],
+                    [bar[0], bar[1], bar[2] * fraction.max(0.0) as f64, bar[3]],
+                    c.transform,
+                    g,
+                );
+                self.font
+                    .render_lines(
+                        name,
+                        Layout::Centered,
+                        (width / 2.0, height - 60.0),
+                        color::WHITE,
+                        &c,
+                        g,
+                    )
+                    .unwrap();
+            }
+
+            // Score and multiplier, top-right.
+            self.font
+                .render_lines(
+                    &hud,
+                    Layout::LeftAlign,
+                    (width - hud_width / 2.0 - 10.0, 30.0),
+                    color::WHITE,
+                    &c,
+                    g,
+                )
+                .unwrap();
+        });
+
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.drawn = drawn;
+            benchmark.end_frame();
+        }
+    }
+
+    fn on_quit(&mut self) {
+        if self.can_suspend() {
+            if let Err(e) = self.suspend() {
+                log::error!("Could not save game: {}", e);
+            }
+        }
+    }
+
+    fn on_game_over(&mut self) {
+        if let Some(bot) = &mut self.bot {
+            if let Err(e) = bot.finish(&self.sim) {
+                log::info!("Bot left before the end: {}", e);
+            }
+        }
+    }
+
+    fn replay(&self) -> Option<&Replay> {
+        Some(&self.sim.replay)
     }
 
     fn on_event(&mut self, event: Event) {
+        let bindings = self.bindings;
+        if self.is_demo {
+            let is_pressed = event
+                .button_args()
+                .is_some_and(|args| args.state == ButtonState::Press);
+            if is_pressed {
+                self.bus
+                    .lock()
+                    .unwrap()
+                    .send(SceneEvent::GoToTitle)
+                    .unwrap();
+            }
+            return;
+        }
+
         match event {
-            // start
-            button_press!(button) if button == 7 => self.toggle_pause(),
-            // right bumper
-            button_press!(button) if button == 5 => self.controller.press_right_bumper(),
-            button_release!(button) if button == 5 => self.controller.release_right_bumper(),
+            button_press!(b) if b == bindings.pause => self.toggle_pause(),
+            button_press!(b) if b == bindings.finish && self.is_paused => self.save_and_quit(),
+            button_press!(b) if b == bindings.finish => self.finish(),
+            button_press!(b) if b == bindings.switch_weapon => {
+                self.controller.press_switch_weapon()
+            }
+            button_release!(b) if b == bindings.switch_weapon => {
+                self.controller.release_switch_weapon()
+            }
+            button_press!(b) if b == bindings.assist => self.toggle_assist(),
+            button_press!(b) if b == bindings.fire => self.controller.press_fire(),
+            button_release!(b) if b == bindings.fire => self.controller.release_fire(),
             // thumbsticks
             axis!(axis, position) => {
                 self.controller.update_axis(axis, position);
@@ -116,14 +842,14 @@ impl Scene for GameScene {
     }
 }
 
-fn update_schedule(clock: &Clock) -> Schedule {
-    Schedule::builder()
-        .add_system(system::player::player_system(system::player::State {
-            last_fire_time: clock.now,
-        }))
-        .add_system(system::movement::movement_system())
-        .add_system(system::collision::collision_system())
-        .add_system(system::spawn_asteroid::create_spawn_timeout_system())
-        .add_system(system::spawn_asteroid::spawn_asteroids_system())
-        .build()
+/// Formats a duration as minutes and seconds, e.g. "1:05".
+fn format_time(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Whether an invulnerable entity is in the "off" phase of its blink.
+fn is_blink(health: &Health, now: Instant) -> bool {
+    let remaining = health.invulnerable_until.unwrap_or(now) - now;
+    remaining.as_millis() / 100 % 2 == 1
 }
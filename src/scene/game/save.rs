@@ -0,0 +1,347 @@
+use std::{
+    error::Error,
+    fmt, fs,
+    io::{self, ErrorKind},
+    rc::Rc,
+    sync::{mpsc::Sender, Arc, Mutex},
+};
+
+use legion::{serialize::Canon, Registry, Resources, World};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use crate::{
+    component::GameOverTimeout,
+    font::Font,
+    profile::Profiles,
+    replay::Replay,
+    resource::{
+        bounds::Bounds,
+        camera::Camera,
+        clock::Clock,
+        game_mode::GameMode,
+        particle_budget::ParticleBudget,
+        random::{Cosmetic, Random},
+        score::Score,
+        scoring::Scoring,
+        textures::Textures,
+        tuning::Tuning,
+        wave::Wave,
+    },
+    scene::SceneEvent,
+    storage,
+};
+
+use super::{GameScene, Simulation};
+
+const SAVE_FILE: &str = "save.json";
+/// Bumped whenever the layout of a save changes, so that a save from another
+/// version is refused rather than restored incorrectly.
+const SAVE_VERSION: u32 = 5;
+
+/// A suspended game, restored exactly as it was left.
+#[derive(Serialize, Deserialize)]
+struct SaveGame {
+    version: u32,
+    profile: String,
+    mode: GameMode,
+    bounds: Bounds,
+    tuning: Tuning,
+    clock: Clock,
+    rng: Random,
+    cosmetic: Cosmetic,
+    score: Score,
+    scoring: Scoring,
+    wave: Wave,
+    camera: Camera,
+    budget: ParticleBudget,
+    replay: Replay,
+    world: serde_json::Value,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    /// The save was written in a format this version cannot read.
+    Version(u32),
+    Invalid(serde_json::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "Could not read the saved game: {}", e),
+            LoadError::Version(version) if *version > SAVE_VERSION => write!(
+                f,
+                "The saved game is from a newer version of Rust Rocks (format {})",
+                version
+            ),
+            LoadError::Version(version) => write!(
+                f,
+                "The saved game is from an older version of Rust Rocks (format {})",
+                version
+            ),
+            LoadError::Invalid(e) => write!(f, "The saved game is damaged: {}", e),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Invalid(e)
+    }
+}
+
+impl SaveGame {
+    /// Captures the simulation as it stands, on behalf of the named profile.
+    fn of(sim: &Simulation, profile: String) -> serde_json::Result<Self> {
+        let registry = registry();
+        let canon = Canon::default();
+        let world = sim.world.as_serializable(legion::any(), &registry, &canon);
+
+        Ok(Self {
+            version: SAVE_VERSION,
+            profile,
+            mode: sim.mode(),
+            bounds: *sim.resources.get::<Bounds>().unwrap(),
+            tuning: *sim.resources.get::<Tuning>().unwrap(),
+            clock: sim.clock,
+            rng: sim.resources.get::<Random>().unwrap().clone(),
+            cosmetic: sim.resources.get::<Cosmetic>().unwrap().clone(),
+            score: sim.score(),
+            scoring: *sim.resources.get::<Scoring>().unwrap(),
+            wave: *sim.resources.get::<Wave>().unwrap(),
+            camera: *sim.resources.get::<Camera>().unwrap(),
+            budget: *sim.resources.get::<ParticleBudget>().unwrap(),
+            replay: sim.replay.clone(),
+            world: serde_json::to_value(&world)?,
+        })
+    }
+
+    /// Picks the simulation up where it was left. Scene events go to the
+    /// given bus.
+    fn into_simulation(self, bus: Arc<Mutex<Sender<SceneEvent>>>) -> Result<Simulation, LoadError> {
+        let world: World = registry()
+            .as_deserialize(&Canon::default())
+            .deserialize(self.world)?;
+
+        let mut resources = Resources::default();
+        resources.insert(self.bounds);
+        resources.insert(self.tuning.clamped());
+        resources.insert(self.mode);
+        resources.insert(self.rng);
+        resources.insert(self.cosmetic);
+        resources.insert(bus);
+        resources.insert(self.score);
+        resources.insert(self.scoring);
+        resources.insert(self.budget);
+        resources.insert(self.wave);
+        resources.insert(self.camera);
+
+        Ok(Simulation::from_parts(
+            world,
+            resources,
+            self.clock,
+            self.replay,
+        ))
+    }
+}
+
+/// Every component which is saved along with the world.
+fn registry() -> Registry<String> {
+    let mut registry = Registry::default();
+    macro_rules! register {
+        ($($component:ty => $name:literal,)*) => {
+            $(registry.register::<$component>($name.to_owned());)*
+        };
+    }
+    components!(register);
+    registry
+}
+
+impl GameScene {
+    pub fn has_save() -> bool {
+        storage::data_dir().join(SAVE_FILE).exists()
+    }
+
+    /// Whether the game may be suspended. Daily challenges may not, since each
+    /// day's challenge is a single sitting, nor may versus, since the other
+    /// player is still playing, and neither may a game which is already over.
+    pub fn can_suspend(&self) -> bool {
+        use legion::IntoQuery;
+
+        self.is_local()
+            && self.mode.seats() == 1
+            && self.mode != GameMode::Daily
+            && <&GameOverTimeout>::query().iter(&self.sim.world).count() == 0
+    }
+
+    /// Moves an unusable save out of the way, keeping it for inspection.
+    pub fn set_aside_save() {
+        if let Err(e) = storage::set_aside(SAVE_FILE) {
+            log::error!("Could not set aside saved game: {}", e);
+        }
+    }
+
+    /// Saves the game so that it can be resumed later.
+    pub fn suspend(&self) -> io::Result<()> {
+        storage::save(SAVE_FILE, &SaveGame::of(&self.sim, self.profile.clone())?)
+    }
+
+    /// Restores the suspended game, switching to the profile which suspended
+    /// it if that profile still exists. The save is used up, so that a game can
+    /// only be resumed once; suspending it again makes a new save.
+    pub fn resume(
+        textures: Rc<Textures>,
+        font: Rc<Font>,
+        profiles: &mut Profiles,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Result<Self, LoadError> {
+        let save = load()?;
+        if let Some(index) = profiles
+            .profiles
+            .iter()
+            .position(|profile| profile.name == save.profile)
+        {
+            profiles.select(index);
+        }
+        let profile = profiles.current();
+        let sim = save.into_simulation(Arc::clone(&bus))?;
+
+        if let Err(e) = fs::remove_file(storage::data_dir().join(SAVE_FILE)) {
+            log::error!("Could not remove saved game: {}", e);
+        }
+
+        Ok(Self {
+            // Give the player a moment to get their bearings.
+            is_paused: true,
+            ..Self::with_simulation(textures, font, sim, profile, bus)
+        })
+    }
+}
+
+fn load() -> Result<SaveGame, LoadError> {
+    let save: serde_json::Value = storage::load(SAVE_FILE)?
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "no saved game"))?;
+
+    let version = save
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0) as u32;
+    if version != SAVE_VERSION {
+        return Err(LoadError::Version(version));
+    }
+
+    Ok(serde_json::from_value(save)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use legion::IntoQuery;
+
+    use crate::{
+        autopilot::Autopilot,
+        bot::{Observation, TIMESTEP},
+        component::Spatial,
+    };
+
+    use super::*;
+
+    /// Updates played in every game.
+    const TICKS: u64 = 600;
+
+    fn bus() -> Arc<Mutex<Sender<SceneEvent>>> {
+        Arc::new(Mutex::new(mpsc::channel().0))
+    }
+
+    fn play(sim: &mut Simulation, pilot: &Autopilot, ticks: u64) {
+        for _ in 0..ticks {
+            let controller = pilot.control(&Observation::of(sim));
+            sim.step(TIMESTEP, &[controller]);
+        }
+    }
+
+    /// Everything about the game which shows whether two games went the same
+    /// way: the score, and where everything is, in the order the systems come
+    /// across it.
+    fn outcome(sim: &Simulation) -> (Score, Vec<(f32, f32)>) {
+        let entities = <&Spatial>::query()
+            .iter(&sim.world)
+            .map(|spatial| (spatial.x, spatial.y))
+            .collect();
+        (sim.score(), entities)
+    }
+
+    /// Suspends the game to JSON and resumes it, as if the game had been
+    /// closed in between.
+    fn round_trip(sim: &Simulation) -> Simulation {
+        let json = serde_json::to_vec(&SaveGame::of(sim, "Player".to_owned()).unwrap()).unwrap();
+        let save: SaveGame = serde_json::from_slice(&json).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        save.into_simulation(bus()).unwrap()
+    }
+
+    #[test]
+    fn resumes_the_game_as_it_was() {
+        let mut sim = Simulation::new(
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+            Tuning::new(),
+            GameMode::Classic,
+            5,
+            bus(),
+        );
+        play(&mut sim, &Autopilot::new(0), 200);
+
+        let resumed = round_trip(&sim);
+        assert_eq!(outcome(&resumed), outcome(&sim));
+        assert_eq!(resumed.replay.hash(), sim.replay.hash());
+        assert_eq!(resumed.clock.elapsed(), sim.clock.elapsed());
+    }
+
+    #[test]
+    fn resumed_games_play_out_as_if_never_suspended() {
+        for mode in [GameMode::Classic, GameMode::Survival, GameMode::Zen] {
+            for seed in [1, 42] {
+                for suspend_at in [1, 300] {
+                    let start = || {
+                        Simulation::new(
+                            Bounds::new(0.0, 0.0, 800.0, 600.0),
+                            Tuning::new(),
+                            mode,
+                            seed,
+                            bus(),
+                        )
+                    };
+                    let pilot = Autopilot::new(0);
+
+                    let mut expected = start();
+                    play(&mut expected, &pilot, TICKS);
+
+                    let mut sim = start();
+                    play(&mut sim, &pilot, suspend_at);
+                    let mut resumed = round_trip(&sim);
+                    play(&mut resumed, &pilot, TICKS - suspend_at);
+
+                    assert_eq!(
+                        outcome(&resumed),
+                        outcome(&expected),
+                        "{:?} seed {} suspended at {}",
+                        mode,
+                        seed,
+                        suspend_at
+                    );
+                    assert_eq!(resumed.replay.hash(), expected.replay.hash());
+                }
+            }
+        }
+    }
+}
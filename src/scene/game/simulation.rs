@@ -0,0 +1,232 @@
+use std::{
+    sync::{mpsc::Sender, Arc, Mutex},
+    time::Duration,
+};
+
+use legion::{world::Duplicate, Resources, Schedule, World};
+
+use crate::{
+    component::Health,
+    controller::{ControllerState, Controllers},
+    replay::Replay,
+    resource::{
+        bounds::Bounds,
+        camera::Camera,
+        clock::Clock,
+        game_mode::GameMode,
+        particle_budget::ParticleBudget,
+        random::{self, Cosmetic, Random},
+        score::Score,
+        scoring::{Breakdown, Scoring},
+        tuning::Tuning,
+        wave::Wave,
+    },
+    scene::SceneEvent,
+};
+
+use super::{entity, system};
+
+/// The game itself, free of any window, textures or input devices, so that it
+/// can be run headlessly as well as played. Each step is entirely determined by
+/// the game's seed and the controller state, and is recorded in the replay.
+pub struct Simulation {
+    pub world: World,
+    pub resources: Resources,
+    pub clock: Clock,
+    pub replay: Replay,
+    schedule: Schedule,
+}
+
+/// The state of a simulation between updates, which it can later be rewound
+/// to.
+pub struct Snapshot {
+    world: World,
+    clock: Clock,
+    /// The length of the replay at the time.
+    ticks: u64,
+    rng: Random,
+    cosmetic: Cosmetic,
+    score: Score,
+    scoring: Scoring,
+    wave: Wave,
+    camera: Camera,
+    budget: ParticleBudget,
+}
+
+impl Simulation {
+    /// Starts a new game. Scene events, such as the end of the game, are sent
+    /// to the given bus.
+    pub fn new(
+        view: Bounds,
+        tuning: Tuning,
+        mode: GameMode,
+        seed: u64,
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+    ) -> Self {
+        let tuning = tuning.clamped();
+
+        // The arena may be larger than the window, in which case the camera
+        // follows the player around it.
+        let bounds = Bounds::new(
+            0.0,
+            0.0,
+            view.inner.width * tuning.arena_scale,
+            view.inner.height * tuning.arena_scale,
+        );
+
+        let mut rng = random::seeded(seed);
+        let mut world = World::default();
+
+        for _ in 0..tuning.asteroids {
+            world.push(entity::asteroid::new(
+                &bounds,
+                tuning.asteroid_speed,
+                &mut rng,
+            ));
+        }
+        // Players line up across the middle of the arena.
+        let seats = mode.seats();
+        for seat in 0..seats {
+            let x = bounds.inner.width * (seat + 1) as f32 / (seats + 1) as f32;
+            let player = world.push(entity::player::new(x, bounds.inner.height / 2.0, seat));
+            if !mode.can_die() {
+                world.entry(player).unwrap().remove_component::<Health>();
+            }
+        }
+
+        let mut resources = Resources::default();
+        resources.insert(bounds);
+        resources.insert(tuning);
+        resources.insert(mode);
+        resources.insert(rng);
+        resources.insert(Cosmetic::seeded(seed));
+        resources.insert(bus);
+        resources.insert(Score::new());
+        resources.insert(Scoring::new());
+        resources.insert(ParticleBudget::new());
+        resources.insert(Wave::new());
+        resources.insert(Camera::new(
+            bounds.inner.width / 2.0,
+            bounds.inner.height / 2.0,
+            view.inner.width,
+            view.inner.height,
+        ));
+
+        Self::from_parts(
+            world,
+            resources,
+            Clock::new(),
+            Replay::new(seed, mode, tuning, view),
+        )
+    }
+
+    /// Picks up a game part-way through, such as one restored from a save.
+    pub fn from_parts(world: World, resources: Resources, clock: Clock, replay: Replay) -> Self {
+        Self {
+            world,
+            resources,
+            schedule: schedule(&clock),
+            clock,
+            replay,
+        }
+    }
+
+    /// Advances the game by one update, given the controller in each seat.
+    pub fn step(&mut self, dt: Duration, controllers: &[ControllerState]) {
+        self.replay.record(dt, controllers);
+        self.clock.update(dt);
+        self.resources.insert(self.clock);
+        self.resources.insert(Controllers(controllers.to_vec()));
+
+        self.schedule.execute(&mut self.world, &mut self.resources);
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            world: duplicate(&self.world),
+            clock: self.clock,
+            ticks: self.replay.ticks(),
+            rng: self.resources.get::<Random>().unwrap().clone(),
+            cosmetic: self.resources.get::<Cosmetic>().unwrap().clone(),
+            score: self.score(),
+            scoring: *self.resources.get::<Scoring>().unwrap(),
+            wave: *self.resources.get::<Wave>().unwrap(),
+            camera: *self.resources.get::<Camera>().unwrap(),
+            budget: *self.resources.get::<ParticleBudget>().unwrap(),
+        }
+    }
+
+    /// Rewinds the game to the moment the snapshot was taken. The updates
+    /// since are forgotten by the replay, too.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.world = duplicate(&snapshot.world);
+        self.clock = snapshot.clock;
+        self.replay.truncate(snapshot.ticks);
+        self.resources.insert(snapshot.rng.clone());
+        self.resources.insert(snapshot.cosmetic.clone());
+        self.resources.insert(snapshot.score);
+        self.resources.insert(snapshot.scoring);
+        self.resources.insert(snapshot.wave);
+        self.resources.insert(snapshot.camera);
+        self.resources.insert(snapshot.budget);
+        // Some systems keep their own state, which must be rewound as well.
+        self.schedule = schedule(&self.clock);
+    }
+
+    pub fn mode(&self) -> GameMode {
+        *self.resources.get::<GameMode>().unwrap()
+    }
+
+    pub fn score(&self) -> Score {
+        *self.resources.get::<Score>().unwrap()
+    }
+
+    pub fn breakdown(&self) -> Breakdown {
+        self.resources.get::<Scoring>().unwrap().breakdown
+    }
+}
+
+/// Copies every entity of a world into a new one. The copy is laid out exactly
+/// like the original, so that it goes on to play out exactly the same way.
+fn duplicate(source: &World) -> World {
+    let mut merger = Duplicate::default();
+    macro_rules! register {
+        ($($component:ty => $name:literal,)*) => {
+            $(merger.register_copy::<$component>();)*
+        };
+    }
+    components!(register);
+
+    let mut world = World::default();
+    world.clone_from(source, &legion::any(), &mut merger);
+    world
+}
+
+/// Systems run each update, starting from the given time.
+fn schedule(clock: &Clock) -> Schedule {
+    Schedule::builder()
+        .add_system(system::player::player_system())
+        .add_system(system::weapon::weapon_system())
+        .add_system(system::homing::homing_system())
+        .add_system(system::hazard::hazard_system())
+        .add_system(system::movement::movement_system())
+        .add_system(system::bounce::bounce_system())
+        .add_system(system::collision::collision_system())
+        .add_system(system::laser::laser_system())
+        .add_system(system::boss::boss_system())
+        .add_system(system::health::health_system())
+        .add_system(system::wave::wave_system())
+        .add_system(system::score::near_miss_system())
+        .add_system(system::score::expire_popups_system())
+        .add_system(system::particle::emit_system())
+        .add_system(system::particle::expire_particles_system())
+        .add_system(system::game_over::time_limit_system())
+        .add_system(system::game_over::survival_system(
+            clock.elapsed().as_secs(),
+        ))
+        .add_system(system::game_over::game_over_system())
+        .add_system(system::camera::camera_system())
+        .add_system(system::spawn_asteroid::create_spawn_timeout_system())
+        .add_system(system::spawn_asteroid::spawn_asteroids_system())
+        .build()
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Armor, AsteroidSize, Boss, BossKind, Health, Player, Spatial, Weapon, WeaponKind},
+    resource::{camera::Camera, clock::Clock, random::Random},
+    scene::game::entity,
+};
+
+/// Acceleration of an enraged boss toward the player, in pixels per second
+/// squared.
+const CHARGE_ACCELERATION: f32 = 60.0;
+const CHARGE_SPEED: f32 = 140.0;
+
+/// Drives boss behavior. Bosses enter a new phase each time they lose a third
+/// of their health.
+#[system]
+#[read_component(Entity)]
+#[read_component(Player)]
+#[read_component(Health)]
+#[write_component(Spatial)]
+#[write_component(Boss)]
+pub fn boss(
+    world: &mut SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] camera: &mut Camera,
+    #[resource] rng: &mut Random,
+) {
+    let player = <(&Spatial, &Player)>::query()
+        .iter(world)
+        .next()
+        .map(|(spatial, _)| (spatial.x, spatial.y));
+
+    <(Entity, &mut Spatial, &Health, &mut Boss)>::query().for_each_mut(
+        world,
+        |(entity, spatial, health, boss)| {
+            let phase = phase(health);
+            if phase > boss.phase {
+                boss.phase = phase;
+                camera.add_trauma(0.6);
+                cmd.push(entity::emitter::explosion(spatial, 64.0));
+                enter_phase(cmd, *entity, spatial, boss);
+            }
+
+            match boss.kind {
+                BossKind::ArmoredAsteroid => {
+                    armored_asteroid(cmd, spatial, boss, player, clock, rng)
+                }
+            }
+        },
+    );
+}
+
+/// Phases run from 1 to 3 as the boss loses health.
+fn phase(health: &Health) -> u8 {
+    let lost = 1.0 - (health.hp / health.max).max(0.0);
+    1 + ((lost * 3.0) as u8).min(2)
+}
+
+fn enter_phase(cmd: &mut CommandBuffer, entity: Entity, spatial: &mut Spatial, boss: &Boss) {
+    match (boss.kind, boss.phase) {
+        (BossKind::ArmoredAsteroid, 2) => {
+            // Turrets emerge, spraying slow shots as the boss spins.
+            spatial.spin *= 2.0;
+            let mut turret = Weapon::new(WeaponKind::Spread);
+            turret.projectile.speed = 250.0;
+            turret.projectile.radius = Some(6.0);
+            turret.muzzle = entity::boss::ARMORED_ASTEROID_RADIUS;
+            turret.cooldown = Duration::from_millis(1200);
+            turret.heat_per_volley = 0.0;
+            turret.trigger = true;
+            cmd.add_component(entity, turret);
+        }
+        (BossKind::ArmoredAsteroid, _) => {
+            // The shell cracks open.
+            spatial.spin *= 2.0;
+            cmd.remove_component::<Armor>(entity);
+        }
+    }
+}
+
+fn armored_asteroid(
+    cmd: &mut CommandBuffer,
+    spatial: &mut Spatial,
+    boss: &mut Boss,
+    player: Option<(f32, f32)>,
+    clock: &Clock,
+    rng: &mut Random,
+) {
+    let radius = entity::boss::ARMORED_ASTEROID_RADIUS;
+
+    if clock.now >= boss.next_shed {
+        let (size, interval) = match boss.phase {
+            1 => (AsteroidSize::Small, 3.0),
+            2 => (AsteroidSize::Medium, 2.0),
+            _ => (AsteroidSize::Small, 1.0),
+        };
+        boss.next_shed = clock.now + Duration::from_secs_f32(interval);
+        cmd.push(entity::asteroid::shed(spatial, radius, size, rng));
+    }
+
+    // In its final phase, the boss charges the player.
+    if let (3, Some((x, y))) = (boss.phase, player) {
+        let heading = (y - spatial.y).atan2(x - spatial.x);
+        let dt = clock.dt.as_secs_f32();
+        spatial.dx += heading.cos() * CHARGE_ACCELERATION * dt;
+        spatial.dy += heading.sin() * CHARGE_ACCELERATION * dt;
+
+        let speed = spatial.dx.hypot(spatial.dy);
+        if speed > CHARGE_SPEED {
+            spatial.dx *= CHARGE_SPEED / speed;
+            spatial.dy *= CHARGE_SPEED / speed;
+        }
+    }
+}
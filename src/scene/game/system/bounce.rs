@@ -0,0 +1,95 @@
+use legion::{system, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Body, HitMask, Spatial},
+    resource::tuning::Tuning,
+};
+
+/// Coefficient of friction between colliding bodies.
+const FRICTION: f32 = 0.3;
+
+/// Resolves collisions between physical bodies as perfectly elastic
+/// collisions between circles, then pushes overlapping bodies apart so that
+/// they do not stick to one another.
+#[system]
+#[write_component(Spatial)]
+#[read_component(HitMask)]
+#[read_component(Body)]
+pub fn bounce(world: &mut SubWorld, #[resource] tuning: &Tuning) {
+    if !tuning.elastic_collisions {
+        return;
+    }
+
+    let mut bodies: Vec<(Entity, Spatial, f32, f32)> =
+        <(Entity, &Spatial, &HitMask, &Body)>::query()
+            .iter(world)
+            .filter_map(|(entity, spatial, mask, body)| match mask {
+                HitMask::Circle { radius } => Some((*entity, *spatial, *radius, body.mass)),
+                HitMask::Point => None,
+            })
+            .collect();
+
+    for i in 0..bodies.len() {
+        for j in (i + 1)..bodies.len() {
+            let (left, right) = bodies.split_at_mut(j);
+            let (_, a, ra, ma) = &mut left[i];
+            let (_, b, rb, mb) = &mut right[0];
+            resolve(a, *ra, *ma, b, *rb, *mb);
+        }
+    }
+
+    let mut query = <&mut Spatial>::query();
+    for (entity, spatial, _, _) in bodies.drain(..) {
+        if let Ok(s) = query.get_mut(world, entity) {
+            *s = spatial;
+        }
+    }
+}
+
+fn resolve(a: &mut Spatial, ra: f32, ma: f32, b: &mut Spatial, rb: f32, mb: f32) {
+    let (nx, ny) = (b.x - a.x, b.y - a.y);
+    let distance = nx.hypot(ny);
+    let overlap = ra + rb - distance;
+    if overlap <= 0.0 || distance == 0.0 {
+        return;
+    }
+
+    // Unit normal pointing from a to b.
+    let (nx, ny) = (nx / distance, ny / distance);
+    let (ia, ib) = (1.0 / ma, 1.0 / mb);
+
+    // Separate the bodies in inverse proportion to their masses.
+    let correction = overlap / (ia + ib);
+    a.x -= nx * correction * ia;
+    a.y -= ny * correction * ia;
+    b.x += nx * correction * ib;
+    b.y += ny * correction * ib;
+
+    // Only exchange momentum if the bodies are approaching one another;
+    // otherwise they are already separating and would be pulled back together.
+    let approach = (b.dx - a.dx) * nx + (b.dy - a.dy) * ny;
+    if approach >= 0.0 {
+        return;
+    }
+
+    let impulse = -2.0 * approach / (ia + ib);
+    a.dx -= nx * impulse * ia;
+    a.dy -= ny * impulse * ia;
+    b.dx += nx * impulse * ib;
+    b.dy += ny * impulse * ib;
+
+    // Friction between the surfaces at the point of contact trades linear
+    // momentum for angular momentum. Bodies are treated as uniform discs, whose
+    // moment of inertia is m * r^2 / 2.
+    let (tx, ty) = (-ny, nx);
+    let slip = (b.dx - a.dx) * tx + (b.dy - a.dy) * ty - b.spin * rb - a.spin * ra;
+    let (ja, jb) = (2.0 / (ma * ra * ra), 2.0 / (mb * rb * rb));
+    let friction = (-slip / (ia + ib + ra * ra * ja + rb * rb * jb))
+        .clamp(-FRICTION * impulse, FRICTION * impulse);
+    a.dx -= tx * friction * ia;
+    a.dy -= ty * friction * ia;
+    b.dx += tx * friction * ib;
+    b.dy += ty * friction * ib;
+    a.spin -= ra * friction * ja;
+    b.spin -= rb * friction * jb;
+}
@@ -0,0 +1,25 @@
+use legion::{system, world::SubWorld, IntoQuery};
+
+use crate::{
+    component::{Player, Spatial},
+    resource::{bounds::Bounds, camera::Camera, clock::Clock},
+};
+
+/// Keeps the camera on the player.
+#[system]
+#[read_component(Spatial)]
+#[read_component(Player)]
+pub fn camera(
+    world: &SubWorld,
+    #[resource] camera: &mut Camera,
+    #[resource] bounds: &Bounds,
+    #[resource] clock: &Clock,
+) {
+    let target = <(&Spatial, &Player)>::query()
+        .iter(world)
+        .next()
+        .map(|(spatial, _)| (spatial.x, spatial.y))
+        .unwrap_or((camera.x, camera.y));
+
+    camera.update(target, bounds, clock.dt.as_secs_f32());
+}
@@ -1,56 +1,94 @@
-use std::sync::{mpsc::Sender, Arc, Mutex};
-
 use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
 
 use crate::{
-    component::{Asteroid, Bullet, HitMask, Player, Spatial},
-    resource::score::Score,
-    scene::SceneEvent,
+    component::{Armor, Damage, Faction, Health, HitMask, Player, Spatial},
+    resource::{camera::Camera, clock::Clock},
+    scene::game::entity,
 };
 
+/// Detects collisions between entities of opposing factions and deals any
+/// contact damage they carry to one another. Sparks fly wherever damage is
+/// dealt.
 #[system]
 #[read_component(Entity)]
-#[read_component(Asteroid)]
-#[read_component(Player)]
-#[read_component(Bullet)]
 #[read_component(Spatial)]
 #[read_component(HitMask)]
+#[read_component(Faction)]
+#[read_component(Damage)]
+#[read_component(Player)]
+#[read_component(Armor)]
+#[write_component(Health)]
 pub fn collision(
     world: &mut SubWorld,
     cmd: &mut CommandBuffer,
-    #[resource] score: &mut Score,
-    #[resource] bus: &mut Arc<Mutex<Sender<SceneEvent>>>,
+    #[resource] clock: &Clock,
+    #[resource] camera: &mut Camera,
 ) {
-    let mut asteroids = <(&Spatial, &HitMask, Entity, &Asteroid)>::query();
-    let mut bullets = <(&Spatial, &HitMask, Entity, &Bullet)>::query();
-    let mut players = <(&Spatial, &HitMask, &Player)>::query();
-
-    asteroids.for_each(world, |asteroid| {
-        bullets.for_each(world, |bullet| {
-            if is_collision(asteroid.0, asteroid.1, bullet.0, bullet.1) {
-                cmd.remove(*asteroid.2);
-                cmd.remove(*bullet.2);
-                *score += 1;
+    let colliders: Vec<(Entity, Spatial, HitMask, Faction, Option<Damage>)> =
+        <(Entity, &Spatial, &HitMask, &Faction, Option<&Damage>)>::query()
+            .iter(world)
+            .map(|(entity, spatial, mask, faction, damage)| {
+                (*entity, *spatial, *mask, *faction, damage.copied())
+            })
+            .collect();
+
+    let mut hits: Vec<(Entity, Damage, (f32, f32))> = Vec::new();
+    for (i, a) in colliders.iter().enumerate() {
+        for b in colliders.iter().skip(i + 1) {
+            if a.3 == b.3 || !is_collision(&a.1, &a.2, &b.1, &b.2) {
+                continue;
+            }
+
+            let contact = contact_point(&a.1, &a.2, &b.1);
+            if let Some(damage) = a.4 {
+                hits.push((b.0, damage, contact));
+            }
+            if let Some(damage) = b.4 {
+                hits.push((a.0, damage, contact));
             }
-        });
-
-        let bus = bus.lock().unwrap();
-        players.for_each(world, |player| {
-            if is_collision(asteroid.0, asteroid.1, player.0, player.1) {
-                bus.send(SceneEvent::PlayerHit {
-                    current_score: *score,
-                })
-                .unwrap();
+        }
+    }
+
+    let mut health = <(&mut Health, Option<&Armor>, Option<&Player>)>::query();
+    for (target, damage, (x, y)) in hits {
+        if let Ok((health, armor, player)) = health.get_mut(world, target) {
+            let reduction = armor.map_or(0.0, |armor| armor.reduction);
+            if health.hit(damage.amount * (1.0 - reduction), clock.now) {
+                cmd.push(entity::emitter::impact(x, y));
+                if player.is_some() {
+                    camera.add_trauma(0.5);
+                }
             }
-        })
-    });
+        }
+    }
+}
+
+/// Where the surfaces of two colliding entities meet.
+fn contact_point(p1: &Spatial, m1: &HitMask, p2: &Spatial) -> (f32, f32) {
+    match m1 {
+        HitMask::Point => (p1.x, p1.y),
+        HitMask::Circle { radius } => {
+            let d = distance(p1, p2);
+            if d == 0.0 {
+                (p1.x, p1.y)
+            } else {
+                (
+                    p1.x + (p2.x - p1.x) / d * radius.min(d),
+                    p1.y + (p2.y - p1.y) / d * radius.min(d),
+                )
+            }
+        }
+    }
 }
 
 fn is_collision(p1: &Spatial, m1: &HitMask, p2: &Spatial, m2: &HitMask) -> bool {
     match (m1, m2) {
         (HitMask::Circle { radius }, HitMask::Point) => distance(p1, p2) < *radius,
         (HitMask::Point, HitMask::Circle { radius }) => distance(p1, p2) < *radius,
-        _ => panic!("Unsupported collision between {:?} and {:?}", m1, m2),
+        (HitMask::Circle { radius: r1 }, HitMask::Circle { radius: r2 }) => {
+            distance(p1, p2) < r1 + r2
+        }
+        (HitMask::Point, HitMask::Point) => false,
     }
 }
 
@@ -0,0 +1,76 @@
+use std::sync::{mpsc::Sender, Arc, Mutex};
+
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{GameOverTimeout, Player},
+    resource::{clock::Clock, game_mode::GameMode, score::Score, scoring::Scoring},
+    scene::SceneEvent,
+};
+
+#[system(for_each)]
+#[allow(clippy::too_many_arguments)]
+pub fn game_over(
+    entity: &Entity,
+    timeout: &GameOverTimeout,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] mode: &GameMode,
+    #[resource] score: &Score,
+    #[resource] scoring: &Scoring,
+    #[resource] bus: &Arc<Mutex<Sender<SceneEvent>>>,
+) {
+    if clock.now < timeout.when {
+        return;
+    }
+
+    cmd.remove(*entity);
+    bus.lock()
+        .unwrap()
+        .send(SceneEvent::GameOver {
+            mode: *mode,
+            current_score: *score,
+            breakdown: scoring.breakdown,
+        })
+        .unwrap();
+}
+
+/// Ends timed games when time runs out.
+#[system]
+#[read_component(GameOverTimeout)]
+pub fn time_limit(
+    world: &SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] mode: &GameMode,
+) {
+    let is_ending = <&GameOverTimeout>::query().iter(world).next().is_some();
+    match mode.time_limit() {
+        Some(limit) if clock.elapsed() >= limit && !is_ending => {
+            cmd.push((GameOverTimeout { when: clock.now },));
+        }
+        _ => (),
+    }
+}
+
+/// Awards a point for every second survived in modes which score survival.
+#[system]
+#[read_component(Player)]
+pub fn survival(
+    world: &SubWorld,
+    #[state] scored: &mut u64,
+    #[resource] clock: &Clock,
+    #[resource] mode: &GameMode,
+    #[resource] score: &mut Score,
+) {
+    let is_alive = <&Player>::query().iter(world).next().is_some();
+    if !mode.scores_survival() || !is_alive {
+        return;
+    }
+
+    let seconds = clock.elapsed().as_secs();
+    if seconds > *scored {
+        *score += (seconds - *scored) as u32;
+        *scored = seconds;
+    }
+}
@@ -0,0 +1,56 @@
+use legion::{component, system, world::SubWorld, IntoQuery};
+
+use crate::{
+    component::{Hazard, HazardKind, Spatial},
+    resource::clock::Clock,
+};
+
+/// How close the inverse-square falloff is evaluated to a hazard's center.
+/// Keeps forces finite for anything passing straight through.
+const MIN_DISTANCE: f32 = 32.0;
+
+/// Applies the forces of environmental hazards to everything within reach.
+#[system]
+#[read_component(Hazard)]
+#[write_component(Spatial)]
+pub fn hazard(world: &mut SubWorld, #[resource] clock: &Clock) {
+    let hazards: Vec<(Spatial, Hazard)> = <(&Spatial, &Hazard)>::query()
+        .iter(world)
+        .map(|(spatial, hazard)| (*spatial, *hazard))
+        .collect();
+    if hazards.is_empty() {
+        return;
+    }
+
+    let dt = clock.dt.as_secs_f32();
+    <&mut Spatial>::query()
+        .filter(!component::<Hazard>())
+        .for_each_mut(world, |spatial| {
+            for (center, hazard) in &hazards {
+                let (x, y) = (center.x - spatial.x, center.y - spatial.y);
+                let distance = x.hypot(y);
+                if distance > hazard.radius {
+                    continue;
+                }
+
+                let falloff = distance.max(MIN_DISTANCE).powi(2);
+                match hazard.kind {
+                    HazardKind::GravityWell { strength } => {
+                        let a = strength / falloff * dt;
+                        spatial.dx += x / distance.max(f32::EPSILON) * a;
+                        spatial.dy += y / distance.max(f32::EPSILON) * a;
+                    }
+                    HazardKind::Repulsor { strength } => {
+                        let a = strength / falloff * dt;
+                        spatial.dx -= x / distance.max(f32::EPSILON) * a;
+                        spatial.dy -= y / distance.max(f32::EPSILON) * a;
+                    }
+                    HazardKind::Nebula { drag } => {
+                        let slowdown = (1.0 - drag * dt).max(0.0);
+                        spatial.dx *= slowdown;
+                        spatial.dy *= slowdown;
+                    }
+                }
+            }
+        });
+}
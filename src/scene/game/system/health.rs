@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use legion::{system, systems::CommandBuffer, Entity};
+
+use crate::{
+    component::{
+        Asteroid, Boss, Bullet, DamageOverTime, Faction, GameOverTimeout, Health, HitMask, Missile,
+        Player, ScoreValue, Spatial,
+    },
+    resource::{
+        camera::Camera, clock::Clock, random::Random, score::Score, scoring::Scoring, wave::Wave,
+    },
+    scene::game::entity,
+};
+
+/// How long to linger on the player's destruction before the game ends.
+const GAME_OVER_DELAY: Duration = Duration::from_millis(1500);
+
+/// Applies damage over time and removes entities whose health is exhausted,
+/// awarding points for those with a score value. Destroyed asteroids break
+/// apart into smaller fragments.
+#[system(for_each)]
+#[allow(clippy::too_many_arguments)]
+pub fn health(
+    entity: &Entity,
+    health: &mut Health,
+    dot: Option<&DamageOverTime>,
+    spatial: Option<&Spatial>,
+    hit_mask: Option<&HitMask>,
+    value: Option<&ScoreValue>,
+    asteroid: Option<&Asteroid>,
+    boss: Option<&Boss>,
+    player: Option<&Player>,
+    faction: Option<&Faction>,
+    bullet: Option<&Bullet>,
+    missile: Option<&Missile>,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] score: &mut Score,
+    #[resource] scoring: &mut Scoring,
+    #[resource] camera: &mut Camera,
+    #[resource] rng: &mut Random,
+    #[resource] wave: &mut Wave,
+) {
+    if let Some(dot) = dot {
+        health.drain(dot.per_second * clock.dt.as_secs_f32(), clock.now);
+        if clock.now >= dot.until {
+            cmd.remove_component::<DamageOverTime>(*entity);
+        }
+    }
+
+    if !health.is_dead() {
+        return;
+    }
+
+    cmd.remove(*entity);
+
+    // Projectiles only die by striking something.
+    let is_projectile = bullet.is_some() || missile.is_some();
+    if is_projectile && matches!(faction, Some(Faction::Player(_))) {
+        scoring.breakdown.hits += 1;
+    }
+
+    if let Some(ScoreValue(value)) = value {
+        let (points, multiplier) = scoring.kill(score, *value, clock.now);
+        if let Some(spatial) = spatial {
+            cmd.push(entity::popup::new(
+                spatial.x, spatial.y, points, multiplier, clock.now,
+            ));
+        }
+    }
+
+    if let (Some(asteroid), Some(spatial)) = (asteroid, spatial) {
+        cmd.push(entity::emitter::explosion(spatial, asteroid.size.radius()));
+        camera.add_trauma(asteroid.size.radius() / 160.0);
+        cmd.extend(entity::asteroid::fragments(spatial, asteroid.size, rng));
+        wave.record_kill();
+        scoring.breakdown.asteroids += 1;
+    }
+
+    if let (Some(_), Some(spatial)) = (boss, spatial) {
+        // Each kind of boss explodes as big as it is.
+        if let Some(HitMask::Circle { radius }) = hit_mask {
+            cmd.push(entity::emitter::explosion(spatial, *radius));
+        }
+        camera.add_trauma(1.0);
+    }
+
+    if player.is_some() {
+        if let Some(spatial) = spatial {
+            cmd.push(entity::emitter::ship_explosion(spatial));
+        }
+        camera.add_trauma(1.0);
+        cmd.push((GameOverTimeout {
+            when: clock.now + GAME_OVER_DELAY,
+        },));
+    }
+}
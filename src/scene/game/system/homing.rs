@@ -0,0 +1,63 @@
+use std::f32::consts::{PI, TAU};
+
+use legion::{component, system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Bullet, Emitter, Faction, Health, Missile, Spatial},
+    resource::clock::Clock,
+    scene::game::entity,
+};
+
+/// Steers missiles toward the nearest target of an opposing faction. Missiles
+/// which run out of fuel fizzle out.
+#[system]
+#[read_component(Entity)]
+#[read_component(Faction)]
+#[read_component(Health)]
+#[read_component(Bullet)]
+#[read_component(Missile)]
+#[write_component(Spatial)]
+#[write_component(Emitter)]
+pub fn homing(world: &mut SubWorld, cmd: &mut CommandBuffer, #[resource] clock: &Clock) {
+    let targets: Vec<(f32, f32, Faction)> = <(&Spatial, &Faction, &Health)>::query()
+        .filter(!component::<Bullet>() & !component::<Missile>())
+        .iter(world)
+        .map(|(spatial, faction, _)| (spatial.x, spatial.y, *faction))
+        .collect();
+
+    let dt = clock.dt.as_secs_f32();
+    <(
+        Entity,
+        &mut Spatial,
+        &Missile,
+        &Faction,
+        Option<&mut Emitter>,
+    )>::query()
+    .for_each_mut(world, |(entity, spatial, missile, faction, trail)| {
+        if clock.now >= missile.fuel_until {
+            cmd.remove(*entity);
+            cmd.push(entity::emitter::impact(spatial.x, spatial.y));
+            return;
+        }
+
+        let nearest = targets
+            .iter()
+            .filter(|(_, _, target)| target != faction)
+            .map(|(x, y, _)| (x - spatial.x, y - spatial.y))
+            .min_by(|a, b| a.0.hypot(a.1).total_cmp(&b.0.hypot(b.1)));
+
+        if let Some((x, y)) = nearest {
+            // Turn toward the target by the shortest way around, no faster
+            // than the missile's turn rate allows.
+            let turn = (y.atan2(x) - spatial.angle_o + PI).rem_euclid(TAU) - PI;
+            let limit = missile.turn_rate * dt;
+            spatial.angle_o = (spatial.angle_o + turn.clamp(-limit, limit)).rem_euclid(TAU);
+        }
+
+        spatial.dx = spatial.angle_o.cos() * missile.speed;
+        spatial.dy = spatial.angle_o.sin() * missile.speed;
+        if let Some(trail) = trail {
+            trail.direction = spatial.angle_o + PI;
+        }
+    });
+}
@@ -0,0 +1,106 @@
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+use rand::Rng;
+
+use crate::{
+    component::{Armor, Faction, Health, HitMask, Spatial, Weapon},
+    resource::{clock::Clock, random::Cosmetic},
+    scene::game::entity,
+};
+
+/// Chance per tick of throwing sparks where a beam strikes.
+const SPARK_CHANCE: f64 = 0.3;
+
+/// Traces firing laser beams from their muzzles along the shooter's heading.
+/// Each beam stops at the first target of an opposing faction in its path,
+/// dealing continuous damage to it.
+#[system]
+#[read_component(Entity)]
+#[read_component(Spatial)]
+#[read_component(HitMask)]
+#[read_component(Faction)]
+#[read_component(Armor)]
+#[write_component(Health)]
+#[write_component(Weapon)]
+pub fn laser(
+    world: &mut SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] rng: &mut Cosmetic,
+) {
+    let targets: Vec<(Entity, f32, f32, f32, Faction)> =
+        <(Entity, &Spatial, &HitMask, &Faction, &Health)>::query()
+            .iter(world)
+            .filter_map(|(entity, spatial, mask, faction, _)| match mask {
+                HitMask::Circle { radius } => {
+                    Some((*entity, spatial.x, spatial.y, *radius, *faction))
+                }
+                HitMask::Point => None,
+            })
+            .collect();
+
+    let mut hits: Vec<(Entity, f32, (f32, f32))> = Vec::new();
+    <(&Spatial, &mut Weapon, &Faction)>::query().for_each_mut(
+        world,
+        |(spatial, weapon, faction)| {
+            let range = match weapon.beam {
+                Some(range) => range,
+                None => return,
+            };
+
+            let (sin, cos) = spatial.angle_o.sin_cos();
+            let (ox, oy) = (
+                spatial.x + cos * weapon.muzzle,
+                spatial.y + sin * weapon.muzzle,
+            );
+            let nearest = targets
+                .iter()
+                .filter(|target| target.4 != *faction)
+                .filter_map(|&(entity, x, y, radius, _)| {
+                    raycast((ox, oy), (cos, sin), (x, y), radius).map(|t| (entity, t))
+                })
+                .filter(|&(_, t)| t <= range)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            weapon.beam = Some(nearest.map_or(range, |(_, t)| t));
+            if let Some((target, t)) = nearest {
+                let damage = weapon.projectile.damage * clock.dt.as_secs_f32();
+                hits.push((target, damage, (ox + cos * t, oy + sin * t)));
+            }
+        },
+    );
+
+    let mut health = <(&mut Health, Option<&Armor>)>::query();
+    for (target, damage, (x, y)) in hits {
+        if let Ok((health, armor)) = health.get_mut(world, target) {
+            let reduction = armor.map_or(0.0, |armor| armor.reduction);
+            if health.drain(damage * (1.0 - reduction), clock.now) && rng.0.gen_bool(SPARK_CHANCE) {
+                cmd.push(entity::emitter::impact(x, y));
+            }
+        }
+    }
+}
+
+/// Distance along a ray to where it first meets a circle, if it does. A ray
+/// starting inside the circle meets it immediately.
+fn raycast(
+    origin: (f32, f32),
+    direction: (f32, f32),
+    center: (f32, f32),
+    radius: f32,
+) -> Option<f32> {
+    let (x, y) = (center.0 - origin.0, center.1 - origin.1);
+    let along = x * direction.0 + y * direction.1;
+    let across = x * x + y * y - along * along;
+    if across > radius * radius {
+        return None;
+    }
+
+    let t = along - (radius * radius - across).sqrt();
+    if t >= 0.0 {
+        Some(t)
+    } else if along + (radius * radius - across).sqrt() >= 0.0 {
+        Some(0.0)
+    } else {
+        None
+    }
+}
@@ -1,4 +1,16 @@
+pub mod boss;
+pub mod bounce;
+pub mod camera;
 pub mod collision;
+pub mod game_over;
+pub mod hazard;
+pub mod health;
+pub mod homing;
+pub mod laser;
 pub mod movement;
+pub mod particle;
 pub mod player;
+pub mod score;
 pub mod spawn_asteroid;
+pub mod wave;
+pub mod weapon;
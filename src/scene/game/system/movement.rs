@@ -1,3 +1,5 @@
+use std::f32::consts::TAU;
+
 use legion::{system, systems::CommandBuffer, Entity};
 
 use crate::{
@@ -14,8 +16,9 @@ pub fn movement(
     #[resource] bounds: &Bounds,
     #[resource] clock: &Clock,
 ) {
-    spatial.x = spatial.x + spatial.dx * clock.dt.as_secs_f32();
-    spatial.y = spatial.y + spatial.dy * clock.dt.as_secs_f32();
+    spatial.x += spatial.dx * clock.dt.as_secs_f32();
+    spatial.y += spatial.dy * clock.dt.as_secs_f32();
+    spatial.angle_o = (spatial.angle_o + spatial.spin * clock.dt.as_secs_f32()).rem_euclid(TAU);
 
     match spatial.wrap {
         WrapAround::Destroy => {
@@ -0,0 +1,65 @@
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Emitter, Particle, Spatial},
+    resource::{clock::Clock, particle_budget::ParticleBudget, random::Cosmetic},
+    scene::game::entity,
+};
+
+/// Spawns particles from every active emitter, so long as the particle budget
+/// allows.
+#[system]
+#[read_component(Entity)]
+#[read_component(Particle)]
+#[read_component(Spatial)]
+#[write_component(Emitter)]
+pub fn emit(
+    world: &mut SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] budget: &ParticleBudget,
+    #[resource] rng: &mut Cosmetic,
+) {
+    let mut available = budget
+        .max
+        .saturating_sub(<&Particle>::query().iter(world).count());
+
+    <(Entity, &Spatial, &mut Emitter)>::query().for_each_mut(
+        world,
+        |(entity, spatial, emitter)| {
+            let count = match emitter.burst {
+                Some(count) => {
+                    cmd.remove(*entity);
+                    count as usize
+                }
+                None if emitter.active => {
+                    emitter.pending += emitter.rate * clock.dt.as_secs_f32();
+                    let count = emitter.pending.floor();
+                    emitter.pending -= count;
+                    count as usize
+                }
+                None => 0,
+            };
+
+            let count = count.min(available);
+            available -= count;
+            let particles: Vec<_> = (0..count)
+                .map(|_| entity::emitter::particle(emitter, spatial, clock.now, &mut rng.0))
+                .collect();
+            cmd.extend(particles);
+        },
+    );
+}
+
+/// Removes particles which have outlived their lifetime.
+#[system(for_each)]
+pub fn expire_particles(
+    entity: &Entity,
+    particle: &Particle,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+) {
+    if particle.is_expired(clock.now) {
+        cmd.remove(*entity);
+    }
+}
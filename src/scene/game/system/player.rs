@@ -1,39 +1,31 @@
-use std::{
-    f64::consts::PI,
-    time::{Duration, Instant},
-};
+use std::f64::consts::PI;
 
-use legion::{system, systems::CommandBuffer, world::SubWorld, IntoQuery};
+use legion::system;
 
 use crate::{
-    component::{PlayerInput, Spatial, WrapAround},
-    controller::ControllerState,
-    resource::clock::Clock,
-    scene::game::entity,
+    component::{Emitter, PlayerInput, Spatial, Weapon},
+    controller::Controllers,
+    resource::{clock::Clock, game_mode::GameMode},
 };
 
 const MAX_PLAYER_SPEED: f32 = 600.0;
-const BULLET_SPEED: f32 = 1_000.0;
-
-pub struct State {
-    pub last_fire_time: Instant,
-}
 
-#[system]
-#[write_component(PlayerInput)]
-#[write_component(Spatial)]
+/// Flies each player's ship according to the controller in their seat.
+#[system(for_each)]
 pub fn player(
-    world: &mut SubWorld,
-    buffer: &mut CommandBuffer,
-    #[state] state: &mut State,
+    spatial: &mut Spatial,
+    exhaust: Option<&mut Emitter>,
+    weapon: Option<&mut Weapon>,
+    input: &mut PlayerInput,
     #[resource] clock: &Clock,
-    #[resource] controller: &ControllerState,
+    #[resource] controllers: &Controllers,
+    #[resource] mode: &GameMode,
 ) {
-    // Get the only player.
-    let (spatial, _) = <(&mut Spatial, &PlayerInput)>::query()
-        .iter_mut(world)
-        .next()
-        .unwrap();
+    let controller = controllers
+        .0
+        .get(input.seat as usize)
+        .copied()
+        .unwrap_or_default();
 
     if let Some(r) = radians(controller.right_thumb) {
         spatial.angle_o = r as f32;
@@ -55,18 +47,27 @@ pub fn player(
         }
     }
 
-    if controller.right_bumper && clock.now >= state.last_fire_time {
-        state.last_fire_time = clock.now + Duration::from_secs_f32(0.333);
+    // Exhaust streams out opposite the direction of thrust.
+    if let Some(exhaust) = exhaust {
+        exhaust.active = x.hypot(y) > 0.1;
+        if let Some(r) = radians((-x, -y)) {
+            exhaust.direction = r as f32;
+        }
+    }
 
-        buffer.push(entity::bullet::new(Spatial {
-            x: spatial.x,
-            y: spatial.y,
-            dx: spatial.angle_o.cos() * BULLET_SPEED,
-            dy: spatial.angle_o.sin() * BULLET_SPEED,
-            angle_o: spatial.angle_o,
-            wrap: WrapAround::Destroy,
-        }));
+    if let Some(weapon) = weapon {
+        weapon.trigger = mode.can_shoot() && controller.fire;
+        if controller.switch_weapon && !input.was_switching {
+            // Switching weapons is no way to shed heat or skip a cooldown.
+            *weapon = Weapon {
+                heat: weapon.heat,
+                overheated: weapon.overheated,
+                ready_at: weapon.ready_at,
+                ..Weapon::new(weapon.kind.next())
+            };
+        }
     }
+    input.was_switching = controller.switch_weapon;
 }
 
 fn radians(point: (f64, f64)) -> Option<f64> {
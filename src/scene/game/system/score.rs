@@ -0,0 +1,96 @@
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Asteroid, Grazing, Health, HitMask, Player, ScorePopup, Spatial},
+    resource::{clock::Clock, score::Score, scoring::Scoring},
+    scene::game::entity,
+};
+
+/// How close, in pixels, an asteroid must pass by the player to count as a
+/// near miss.
+const NEAR_MISS_MARGIN: f32 = 24.0;
+
+/// Awards a bonus to the player for letting asteroids pass within a hair's
+/// breadth without being hit.
+#[system]
+#[read_component(Entity)]
+#[read_component(Spatial)]
+#[read_component(HitMask)]
+#[read_component(Health)]
+#[read_component(Player)]
+#[read_component(Asteroid)]
+#[read_component(Grazing)]
+pub fn near_miss(
+    world: &mut SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] score: &mut Score,
+    #[resource] scoring: &mut Scoring,
+) {
+    let player = match <(&Spatial, &Health, &Player)>::query().iter(world).next() {
+        Some((spatial, health, _)) => (*spatial, *health),
+        None => return,
+    };
+    let (player, health) = player;
+
+    <(
+        Entity,
+        &Spatial,
+        &HitMask,
+        &Health,
+        Option<&Grazing>,
+        &Asteroid,
+    )>::query()
+    .for_each(
+        world,
+        |(entity, spatial, mask, asteroid_health, grazing, _)| {
+            // An asteroid shot down this update is only removed once the
+            // update is over. Until then it must not count as a near miss,
+            // or shooting a rock point-blank would score the bonus as well.
+            if asteroid_health.is_dead() {
+                return;
+            }
+
+            let radius = match mask {
+                HitMask::Circle { radius } => *radius,
+                HitMask::Point => 0.0,
+            };
+            let gap = (spatial.x - player.x).hypot(spatial.y - player.y) - radius;
+            let is_close = gap < NEAR_MISS_MARGIN;
+
+            match grazing {
+                None if is_close && !health.is_invulnerable(clock.now) => {
+                    cmd.add_component(
+                        *entity,
+                        Grazing {
+                            player_hp: health.hp,
+                        },
+                    );
+                }
+                Some(grazing) if !is_close => {
+                    cmd.remove_component::<Grazing>(*entity);
+                    if health.hp >= grazing.player_hp {
+                        let (points, multiplier) = scoring.near_miss(score, clock.now);
+                        cmd.push(entity::popup::new(
+                            player.x, player.y, points, multiplier, clock.now,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        },
+    );
+}
+
+/// Removes score popups once they have been displayed long enough.
+#[system(for_each)]
+pub fn expire_popups(
+    entity: &Entity,
+    popup: &ScorePopup,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+) {
+    if clock.now >= popup.until {
+        cmd.remove(*entity);
+    }
+}
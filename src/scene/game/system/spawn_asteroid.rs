@@ -4,7 +4,10 @@ use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery}
 
 use crate::{
     component::{Asteroid, SpawnTimeout},
-    resource::{bounds::Bounds, clock::Clock},
+    resource::{
+        bounds::Bounds, clock::Clock, game_mode::GameMode, random::Random, tuning::Tuning,
+        wave::Wave,
+    },
     scene::game::entity::{self, asteroid::Archetype},
 };
 
@@ -15,12 +18,21 @@ pub fn create_spawn_timeout(
     world: &mut SubWorld,
     cmd: &mut CommandBuffer,
     #[resource] clock: &Clock,
+    #[resource] mode: &GameMode,
+    #[resource] tuning: &Tuning,
+    #[resource] wave: &Wave,
 ) {
     let asteroids =
         <&Asteroid>::query().iter(world).count() + <&SpawnTimeout>::query().iter(world).count();
-    if asteroids < 5 {
+    // Bosses bring their own asteroids.
+    let target = if wave.boss {
+        0
+    } else {
+        mode.asteroid_count(tuning.asteroids, clock.elapsed())
+    };
+    if asteroids < target {
         let when = clock.now + Duration::from_secs(1);
-        let timeouts: Vec<(SpawnTimeout,)> = (0..(5 - asteroids))
+        let timeouts: Vec<(SpawnTimeout,)> = (0..(target - asteroids))
             .map(|_| (SpawnTimeout { when },))
             .collect();
         cmd.extend(timeouts);
@@ -34,6 +46,8 @@ pub fn spawn_asteroids(
     cmd: &mut CommandBuffer,
     #[resource] bounds: &Bounds,
     #[resource] clock: &Clock,
+    #[resource] rng: &mut Random,
+    #[resource] tuning: &Tuning,
 ) {
     let asteroids: Vec<Archetype> = <(&SpawnTimeout, Entity)>::query()
         .iter(world)
@@ -41,7 +55,7 @@ pub fn spawn_asteroids(
         .map(|(_timeout, entity)| {
             cmd.remove(*entity);
         })
-        .map(|_| entity::asteroid::new(bounds))
+        .map(|_| entity::asteroid::new(bounds, tuning.asteroid_speed, rng))
         .collect();
     cmd.extend(asteroids);
 }
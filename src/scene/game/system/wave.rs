@@ -0,0 +1,69 @@
+use legion::{system, systems::CommandBuffer, world::SubWorld, Entity, IntoQuery};
+
+use crate::{
+    component::{Boss, Hazard, Health, Player, Spatial},
+    resource::{
+        bounds::Bounds, camera::Camera, clock::Clock, game_mode::GameMode, random::Random,
+        tuning::Tuning, wave::Wave,
+    },
+    scene::game::entity,
+};
+
+/// Advances through waves, summoning a boss every few waves and rewarding the
+/// player for defeating it. Each new wave brings its own hazards.
+#[system]
+#[read_component(Entity)]
+#[read_component(Boss)]
+#[read_component(Hazard)]
+#[read_component(Spatial)]
+#[read_component(Player)]
+#[write_component(Health)]
+#[allow(clippy::too_many_arguments)]
+pub fn wave(
+    world: &mut SubWorld,
+    cmd: &mut CommandBuffer,
+    #[resource] wave: &mut Wave,
+    #[resource] mode: &GameMode,
+    #[resource] tuning: &Tuning,
+    #[resource] bounds: &Bounds,
+    #[resource] clock: &Clock,
+    #[resource] camera: &mut Camera,
+    #[resource] rng: &mut Random,
+) {
+    let number = wave.number;
+
+    if wave.boss {
+        if <&Boss>::query().iter(world).next().is_some() {
+            return;
+        }
+
+        // The boss has been defeated. Patch up the player's hull.
+        <(&mut Health, &Player)>::query().for_each_mut(world, |(health, _)| {
+            health.hp = (health.hp + 1.0).min(health.max);
+        });
+        wave.advance();
+    } else if wave.is_cleared() && wave.advance() {
+        if mode.has_bosses() {
+            camera.add_trauma(0.4);
+            cmd.push(entity::boss::armored_asteroid(bounds, rng, clock.now));
+        } else {
+            wave.boss = false;
+        }
+    }
+
+    if wave.number != number && tuning.hazards {
+        <(Entity, &Hazard)>::query().for_each(world, |(entity, _)| cmd.remove(*entity));
+
+        let player = <(&Spatial, &Player)>::query()
+            .iter(world)
+            .next()
+            .map(|(spatial, _)| (spatial.x, spatial.y));
+        cmd.extend(entity::hazard::for_wave(
+            wave,
+            &tuning.hazard_layout,
+            bounds,
+            player,
+            rng,
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use legion::{system, systems::CommandBuffer};
+
+use crate::{
+    component::{Faction, ProjectileKind, Spatial, Weapon, WeaponKind, WrapAround, LASER_RANGE},
+    resource::{
+        clock::{Clock, Instant},
+        scoring::Scoring,
+        tuning::Tuning,
+    },
+    scene::game::entity,
+};
+
+/// Fires weapons whose triggers are pulled, and lets them cool off. Laser
+/// beams are traced separately by the laser system.
+#[system(for_each)]
+pub fn weapon(
+    spatial: &Spatial,
+    weapon: &mut Weapon,
+    faction: &Faction,
+    cmd: &mut CommandBuffer,
+    #[resource] clock: &Clock,
+    #[resource] tuning: &Tuning,
+    #[resource] scoring: &mut Scoring,
+) {
+    let cooldown = cooldown(weapon, *faction, tuning);
+    let dt = clock.dt.as_secs_f32();
+    weapon.heat = (weapon.heat - weapon.cooling * dt).max(0.0);
+    if weapon.overheated && weapon.heat == 0.0 {
+        weapon.overheated = false;
+    }
+
+    match weapon.kind {
+        WeaponKind::Laser => {
+            if weapon.trigger && weapon.is_ready(clock.now) {
+                weapon.beam = Some(LASER_RANGE);
+                heat_up(weapon, weapon.heat_per_volley * dt);
+            } else {
+                weapon.beam = None;
+            }
+        }
+        WeaponKind::Charge => {
+            // Charge while the trigger is held; fire on release.
+            if weapon.trigger {
+                if weapon.charging_since.is_none() && weapon.is_ready(clock.now) {
+                    weapon.charging_since = Some(clock.now);
+                }
+            } else if weapon.charging_since.is_some() {
+                let power = weapon.power(clock.now);
+                weapon.charging_since = None;
+                fire(cmd, spatial, weapon, *faction, power, clock.now, scoring);
+                weapon.ready_at = Some(clock.now + cooldown);
+            }
+        }
+        _ => {
+            if weapon.trigger && weapon.pending == 0 && weapon.is_ready(clock.now) {
+                weapon.pending = weapon.burst;
+            }
+
+            if weapon.pending > 0 && weapon.ready_at.is_none_or(|at| clock.now >= at) {
+                fire(cmd, spatial, weapon, *faction, 1.0, clock.now, scoring);
+                weapon.pending -= 1;
+                weapon.ready_at = Some(
+                    clock.now
+                        + if weapon.pending > 0 {
+                            weapon.burst_interval
+                        } else {
+                            cooldown
+                        },
+                );
+                if weapon.overheated {
+                    weapon.pending = 0;
+                }
+            }
+        }
+    }
+}
+
+/// The time between the weapon's volleys, which for the players' weapons
+/// depends on the fire rate they are tuned to.
+fn cooldown(weapon: &Weapon, faction: Faction, tuning: &Tuning) -> Duration {
+    // Dividing by one would still round the cooldown through an f32, and
+    // change the outcome of games recorded before fire rates could be tuned.
+    if faction == Faction::Hostile || tuning.fire_rate == 1.0 {
+        weapon.cooldown
+    } else {
+        weapon.cooldown.div_f32(tuning.fire_rate)
+    }
+}
+
+/// Fires a single volley, scaling its damage and size by the given power.
+fn fire(
+    cmd: &mut CommandBuffer,
+    spatial: &Spatial,
+    weapon: &mut Weapon,
+    faction: Faction,
+    power: f32,
+    now: Instant,
+    scoring: &mut Scoring,
+) {
+    if let Faction::Player(_) = faction {
+        scoring.breakdown.shots += weapon.volley;
+    }
+
+    let projectile = weapon.projectile;
+    let radius = match (projectile.radius, power > 1.0) {
+        (Some(radius), _) => Some(radius * power),
+        (None, true) => Some(4.0 * power),
+        (None, false) => None,
+    };
+
+    let (sin, cos) = spatial.angle_o.sin_cos();
+    let middle = (weapon.volley - 1) as f32 / 2.0;
+    for i in 0..weapon.volley {
+        let offset = i as f32 - middle;
+        let angle = spatial.angle_o + offset * weapon.spread;
+        let spatial = Spatial {
+            x: spatial.x + cos * weapon.muzzle - sin * offset * weapon.gap,
+            y: spatial.y + sin * weapon.muzzle + cos * offset * weapon.gap,
+            dx: angle.cos() * projectile.speed,
+            dy: angle.sin() * projectile.speed,
+            angle_o: angle,
+            spin: 0.0,
+            wrap: WrapAround::Destroy,
+        };
+        let damage = projectile.damage * power;
+        match projectile.kind {
+            ProjectileKind::Bullet => {
+                cmd.push(entity::bullet::new(spatial, faction, damage, radius));
+            }
+            ProjectileKind::Missile => {
+                cmd.push(entity::missile::new(
+                    spatial,
+                    faction,
+                    damage,
+                    projectile.speed,
+                    now,
+                ));
+            }
+        }
+    }
+
+    heat_up(weapon, weapon.heat_per_volley);
+}
+
+fn heat_up(weapon: &mut Weapon, heat: f32) {
+    weapon.heat += heat;
+    if weapon.heat >= 1.0 {
+        weapon.heat = 1.0;
+        weapon.overheated = true;
+    }
+}
@@ -0,0 +1,168 @@
+use std::{collections::HashMap, f64::consts::TAU, mem};
+
+use graphics::{line, Context, Transformed};
+use legion::{IntoQuery, World};
+use opengl_graphics::GlGraphics;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+use crate::{
+    component::{Asteroid, Faction, Health, HitMask, Spatial, Sprite, SpriteKind},
+    resource::clock::Instant,
+};
+
+use super::{is_blink, SEAT_COLORS};
+
+/// Corners around an asteroid's outline.
+const ASTEROID_CORNERS: usize = 11;
+/// How far an asteroid's corners may sink below its surface, as a fraction of
+/// its radius.
+const JAGGEDNESS: f64 = 0.3;
+/// Corners around a boss's outline, alternately raised and sunk like armor
+/// plating.
+const BOSS_CORNERS: usize = 16;
+/// Radius of the smallest bullets, which hit as a point.
+const BULLET_RADIUS: f64 = 2.0;
+/// Width of the glow around each line, either side of it.
+const GLOW: f64 = 2.5;
+/// The players' ships, pointing along x.
+const SHIP: [[f64; 2]; 4] = [[16.0, 0.0], [-12.0, 11.0], [-6.0, 0.0], [-12.0, -11.0]];
+
+const ASTEROID_COLOR: [f32; 4] = [0.7, 0.85, 1.0, 1.0];
+const BOSS_COLOR: [f32; 4] = [1.0, 0.4, 0.3, 1.0];
+const HOSTILE_BULLET_COLOR: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+
+/// Asteroid outlines, kept from frame to frame rather than traced afresh each
+/// time. Outlines which go a frame without being drawn are forgotten.
+#[derive(Default)]
+pub struct Outlines {
+    /// Outlines drawn this frame, by shape and radius.
+    drawn: HashMap<(u32, u64), Vec<[f64; 2]>>,
+    /// Outlines drawn the frame before.
+    previous: HashMap<(u32, u64), Vec<[f64; 2]>>,
+}
+
+impl Outlines {
+    fn start_frame(&mut self) {
+        self.previous = mem::take(&mut self.drawn);
+    }
+
+    /// The outline of an asteroid of the given radius and shape.
+    fn jagged(&mut self, radius: f64, shape: u32) -> &[[f64; 2]] {
+        let key = (shape, radius.to_bits());
+        let previous = &mut self.previous;
+        self.drawn.entry(key).or_insert_with(|| {
+            previous
+                .remove(&key)
+                .unwrap_or_else(|| jagged(radius, shape))
+        })
+    }
+}
+
+/// Draws every entity with a sprite as a glowing outline instead. Returns the
+/// number of outlines drawn.
+pub fn draw(
+    world: &World,
+    now: Instant,
+    outlines: &mut Outlines,
+    view: &Context,
+    g: &mut GlGraphics,
+) -> usize {
+    outlines.start_frame();
+    let mut drawn = 0;
+    <(
+        &Spatial,
+        &Sprite,
+        Option<&HitMask>,
+        Option<&Faction>,
+        Option<&Asteroid>,
+        Option<&Health>,
+    )>::query()
+    .for_each(
+        world,
+        |(spatial, sprite, hit_mask, faction, asteroid, health)| {
+            // Invulnerable entities blink.
+            if let Some(health) = health {
+                if health.is_invulnerable(now) && is_blink(health, now) {
+                    return;
+                }
+            }
+
+            let radius = match hit_mask {
+                Some(HitMask::Circle { radius }) => *radius as f64,
+                Some(HitMask::Point) => BULLET_RADIUS,
+                // Scenery is as big as its sprite.
+                None => sprite.width as f64 / 2.0,
+            };
+            let (outline, color): (&[[f64; 2]], _) = match sprite.kind {
+                SpriteKind::Player => (&SHIP, seat_color(faction)),
+                SpriteKind::Asteroid => (
+                    outlines.jagged(radius, asteroid.map_or(0, |asteroid| asteroid.shape)),
+                    ASTEROID_COLOR,
+                ),
+                SpriteKind::Boss => (&armored(radius), BOSS_COLOR),
+                SpriteKind::Bullet => (
+                    &diamond(radius),
+                    match faction {
+                        Some(Faction::Hostile) => HOSTILE_BULLET_COLOR,
+                        _ => seat_color(faction),
+                    },
+                ),
+                SpriteKind::Title => return,
+            };
+
+            let transform = view
+                .trans(spatial.x as f64, spatial.y as f64)
+                .rot_rad(spatial.angle_o as f64)
+                .transform;
+            let [r, g_, b, _] = color;
+            for (i, from) in outline.iter().enumerate() {
+                let to = outline[(i + 1) % outline.len()];
+                let edge = [from[0], from[1], to[0], to[1]];
+                line([r, g_, b, 0.25], GLOW, edge, transform, g);
+                line(color, 0.75, edge, transform, g);
+            }
+            drawn += 1;
+        },
+    );
+    drawn
+}
+
+/// A rough outline of the given radius, the same for the same shape.
+fn jagged(radius: f64, shape: u32) -> Vec<[f64; 2]> {
+    let mut rng = Pcg32::seed_from_u64(shape as u64);
+    (0..ASTEROID_CORNERS)
+        .map(|i| {
+            let angle = (i as f64 + rng.gen_range(-0.3..0.3)) * TAU / ASTEROID_CORNERS as f64;
+            let distance = radius * (1.0 - rng.gen_range(0.0..JAGGEDNESS));
+            [angle.cos() * distance, angle.sin() * distance]
+        })
+        .collect()
+}
+
+fn armored(radius: f64) -> Vec<[f64; 2]> {
+    (0..BOSS_CORNERS)
+        .map(|i| {
+            let angle = i as f64 * TAU / BOSS_CORNERS as f64;
+            let distance = if i % 2 == 0 { radius } else { radius * 0.85 };
+            [angle.cos() * distance, angle.sin() * distance]
+        })
+        .collect()
+}
+
+fn diamond(radius: f64) -> [[f64; 2]; 4] {
+    [
+        [radius * 1.5, 0.0],
+        [0.0, radius],
+        [-radius * 1.5, 0.0],
+        [0.0, -radius],
+    ]
+}
+
+/// The color of whatever belongs to a player, in their seat's color.
+fn seat_color(faction: Option<&Faction>) -> [f32; 4] {
+    match faction {
+        Some(Faction::Player(seat)) => SEAT_COLORS[*seat as usize % SEAT_COLORS.len()],
+        _ => SEAT_COLORS[0],
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    rc::Rc,
+    sync::{mpsc::Sender, Arc, Mutex},
+};
+
+use graphics::color;
+use opengl_graphics::GlGraphics;
+use piston::{ButtonEvent, Event, HatState, RenderArgs, UpdateArgs};
+
+use crate::{
+    font::{Font, Layout},
+    high_scores::Initials,
+    resource::{game_mode::GameMode, score::Score, scoring::Breakdown},
+};
+
+use super::{
+    letter_picker::LetterPicker,
+    menu::{on_button_press, on_hat_press},
+    Scene, SceneEvent,
+};
+
+/// Lets the player sign a new high score with their initials, using the d-pad
+/// to pick each letter.
+pub struct InitialsScene {
+    font: Rc<Font>,
+    bus: Arc<Mutex<Sender<SceneEvent>>>,
+    mode: GameMode,
+    score: Score,
+    breakdown: Breakdown,
+    picker: LetterPicker<3>,
+}
+
+impl InitialsScene {
+    pub fn new(
+        bus: Arc<Mutex<Sender<SceneEvent>>>,
+        font: Rc<Font>,
+        mode: GameMode,
+        score: Score,
+        breakdown: Breakdown,
+        initials: Initials,
+    ) -> Self {
+        Self {
+            font,
+            bus,
+            mode,
+            score,
+            breakdown,
+            picker: LetterPicker::new(initials.0),
+        }
+    }
+
+    /// Moves to the next letter, or submits the initials after the last.
+    fn confirm(&mut self) {
+        if !self.picker.advance() {
+            return;
+        }
+
+        self.bus
+            .lock()
+            .unwrap()
+            .send(SceneEvent::SignHighScore {
+                mode: self.mode,
+                initials: Initials(self.picker.letters),
+                current_score: self.score,
+                breakdown: self.breakdown,
+            })
+            .unwrap();
+    }
+}
+
+impl Scene for InitialsScene {
+    fn update(&mut self, _args: UpdateArgs) {}
+
+    fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
+        let lines = [
+            self.mode.name().to_owned(),
+            format!("New high score: {}", self.score),
+            String::new(),
+            "Enter your initials".to_owned(),
+            self.picker.label(),
+        ]
+        .iter()
+        .map(|line| self.font.create_text(line).unwrap())
+        .collect();
+
+        gl.draw(args.viewport(), |c, g| {
+            use graphics::clear;
+
+            clear([0.0; 4], g);
+
+            let center = {
+                let [_, _, width, height] = args.viewport().rect;
+                (width as f64 / 2.0, height as f64 / 2.0)
+            };
+
+            self.font
+                .render_lines(&lines, Layout::Centered, center, color::WHITE, &c, g)
+                .unwrap();
+        });
+    }
+
+    fn on_event(&mut self, event: Event) {
+        event.button(|args| {
+            on_hat_press(args, HatState::Up, || self.picker.change_letter(1));
+            on_hat_press(args, HatState::Down, || self.picker.change_letter(-1));
+            on_hat_press(args, HatState::Right, || self.confirm());
+            on_hat_press(args, HatState::Left, || {
+                self.picker.back();
+            });
+            on_button_press(args, 0, || self.confirm());
+            on_button_press(args, 1, || {
+                self.picker.back();
+            });
+        });
+    }
+}
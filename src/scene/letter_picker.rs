@@ -0,0 +1,63 @@
+/// Letters which may be picked, in the order the d-pad cycles through them.
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ ";
+
+/// Spells out a word one letter at a time with the d-pad, arcade style.
+#[derive(Clone, Copy, Debug)]
+pub struct LetterPicker<const N: usize> {
+    pub letters: [u8; N],
+    pub cursor_index: usize,
+}
+
+impl<const N: usize> LetterPicker<N> {
+    pub fn new(letters: [u8; N]) -> Self {
+        Self {
+            letters,
+            cursor_index: 0,
+        }
+    }
+
+    /// Cycles the letter under the cursor through the alphabet.
+    pub fn change_letter(&mut self, by: isize) {
+        let letter = &mut self.letters[self.cursor_index];
+        let index = ALPHABET.iter().position(|x| x == letter).unwrap_or(0) as isize;
+        *letter = ALPHABET[(index + by).rem_euclid(ALPHABET.len() as isize) as usize];
+    }
+
+    /// Moves to the next letter. Returns true if the cursor was already on the
+    /// last letter, meaning the word is finished.
+    pub fn advance(&mut self) -> bool {
+        if self.cursor_index + 1 < N {
+            self.cursor_index += 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Moves to the previous letter. Returns false if the cursor was already on
+    /// the first letter.
+    pub fn back(&mut self) -> bool {
+        if self.cursor_index > 0 {
+            self.cursor_index -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The word so far, with the letter under the cursor bracketed.
+    pub fn label(&self) -> String {
+        self.letters
+            .iter()
+            .enumerate()
+            .map(|(i, &letter)| {
+                let letter = if letter == b' ' { '_' } else { letter as char };
+                if i == self.cursor_index {
+                    format!("[{}]", letter)
+                } else {
+                    format!(" {} ", letter)
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,71 @@
+use piston::{Button, ButtonArgs, ButtonState, ControllerButton, ControllerHat, HatState};
+
+use super::SceneEvent;
+
+pub fn on_hat_press<F>(args: ButtonArgs, state: HatState, callback: F)
+where
+    F: FnOnce(),
+{
+    match args {
+        ButtonArgs {
+            // TODO: possible Piston bug: pressing a hat direction generates the
+            // Release event; releasing a hat does nothing.
+            state: ButtonState::Release,
+            button: Button::Hat(ControllerHat { state: s, .. }),
+            ..
+        } if s == state => callback(),
+        _ => (),
+    }
+}
+
+pub fn on_button_press<F>(args: ButtonArgs, button: u8, callback: F)
+where
+    F: FnOnce(),
+{
+    match args {
+        ButtonArgs {
+            state: ButtonState::Press,
+            button: Button::Controller(ControllerButton { button: b, .. }),
+            ..
+        } if b == button => callback(),
+        _ => (),
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Menu<const L: usize>
+where
+    [(&'static str, SceneEvent); L]: Copy,
+{
+    pub choices: [(&'static str, SceneEvent); L],
+    pub cursor_index: usize,
+}
+
+impl<const L: usize> Menu<L> {
+    pub fn move_cursor_down(&mut self) {
+        self.cursor_index = (self.cursor_index + 1) % self.choices.len();
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        if self.cursor_index == 0 {
+            self.cursor_index = self.choices.len() - 1;
+        } else {
+            self.cursor_index -= 1;
+        }
+    }
+
+    pub fn selected(&self) -> SceneEvent {
+        self.choices.get(self.cursor_index).unwrap().1
+    }
+
+    /// The label of each choice, with the selected choice highlighted.
+    pub fn labels(&self) -> impl Iterator<Item = String> + '_ {
+        self.choices.iter().enumerate().map(move |(i, (label, _))| {
+            if i == self.cursor_index {
+                format!("> {} <", label)
+            } else {
+                label.to_string()
+            }
+        })
+    }
+}
@@ -1,4 +1,9 @@
+mod daily;
 mod game;
+mod initials;
+mod letter_picker;
+mod menu;
+mod profile;
 mod scene_event;
 mod score;
 mod title;
@@ -6,7 +11,12 @@ mod title;
 use opengl_graphics::GlGraphics;
 use piston::{Event, RenderArgs, UpdateArgs};
 
-pub use game::GameScene;
+use crate::replay::Replay;
+
+pub use daily::DailyScene;
+pub use game::{GameScene, Simulation, Snapshot};
+pub use initials::InitialsScene;
+pub use profile::ProfileScene;
 pub use scene_event::SceneEvent;
 pub use score::ScoreScene;
 pub use title::TitleScene;
@@ -17,4 +27,16 @@ pub trait Scene {
     fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics);
 
     fn on_event(&mut self, event: Event);
+
+    /// Called when the game is closing while this scene is active.
+    fn on_quit(&mut self) {}
+
+    /// Called when the game played in this scene is over, before the scene is
+    /// left.
+    fn on_game_over(&mut self) {}
+
+    /// The record of the game played in this scene, if any.
+    fn replay(&self) -> Option<&Replay> {
+        None
+    }
 }
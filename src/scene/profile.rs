@@ -0,0 +1,190 @@
+use std::{
+    rc::Rc,
+    sync::{mpsc::Sender, Arc, Mutex},
+};
+
+use graphics::color;
+use opengl_graphics::GlGraphics;
+use piston::{ButtonEvent, Event, HatState, RenderArgs, UpdateArgs};
+
+use crate::{
+    font::{Font, Layout},
+    profile::{Profiles, NAME_LENGTH},
+};
+
+use super::{
+    letter_picker::LetterPicker,
+    menu::{on_button_press, on_hat_press},
+    Scene, SceneEvent,
+};
+
+/// Lists the player profiles, showing each one's lifetime statistics, and lets
+/// the player switch profiles or create a new one.
+pub struct ProfileScene {
+    font: Rc<Font>,
+    bus: Arc<Mutex<Sender<SceneEvent>>>,
+    profiles: Profiles,
+    /// One past the last profile is "New Profile"; two past is "Back".
+    cursor_index: usize,
+    /// The name of a new profile, while one is being created.
+    naming: Option<LetterPicker<NAME_LENGTH>>,
+}
+
+impl ProfileScene {
+    pub fn new(bus: Arc<Mutex<Sender<SceneEvent>>>, font: Rc<Font>, profiles: Profiles) -> Self {
+        Self {
+            font,
+            bus,
+            cursor_index: profiles.current,
+            profiles,
+            naming: None,
+        }
+    }
+
+    fn choices(&self) -> usize {
+        self.profiles.profiles.len() + 2
+    }
+
+    fn confirm(&mut self) {
+        let count = self.profiles.profiles.len();
+        let event = match &mut self.naming {
+            Some(picker) => {
+                if !picker.advance() {
+                    return;
+                }
+                SceneEvent::CreateProfile {
+                    name: picker.letters,
+                }
+            }
+            None if self.cursor_index < count => SceneEvent::SelectProfile {
+                index: self.cursor_index,
+            },
+            None if self.cursor_index == count => {
+                self.naming = Some(LetterPicker::new([b' '; NAME_LENGTH]));
+                return;
+            }
+            None => SceneEvent::GoToTitle,
+        };
+        self.bus.lock().unwrap().send(event).unwrap();
+    }
+
+    fn back(&mut self) {
+        match &mut self.naming {
+            Some(picker) => {
+                if !picker.back() {
+                    self.naming = None;
+                }
+            }
+            None => self
+                .bus
+                .lock()
+                .unwrap()
+                .send(SceneEvent::GoToTitle)
+                .unwrap(),
+        }
+    }
+
+    fn move_cursor(&mut self, by: isize) {
+        match &mut self.naming {
+            Some(picker) => picker.change_letter(-by),
+            None => {
+                self.cursor_index =
+                    (self.cursor_index as isize + by).rem_euclid(self.choices() as isize) as usize
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        if let Some(picker) = &self.naming {
+            return vec![
+                "New Profile".to_owned(),
+                String::new(),
+                "Enter a name".to_owned(),
+                picker.label(),
+            ];
+        }
+
+        let mut lines = vec!["Profiles".to_owned(), String::new()];
+        let labels = self
+            .profiles
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(i, profile)| {
+                if i == self.profiles.current {
+                    format!("{} *", profile.name)
+                } else {
+                    profile.name.clone()
+                }
+            })
+            .chain(["New Profile".to_owned(), "Back".to_owned()]);
+        lines.extend(labels.enumerate().map(|(i, label)| {
+            if i == self.cursor_index {
+                format!("> {} <", label)
+            } else {
+                label
+            }
+        }));
+
+        if let Some(profile) = self.profiles.profiles.get(self.cursor_index) {
+            let stats = &profile.stats;
+            let minutes = stats.time_played.as_secs() / 60;
+            lines.extend([
+                String::new(),
+                format!("Games: {}", stats.games_played),
+                format!("Total score: {}", stats.total_score),
+                format!("Kills: {}", stats.kills),
+                format!("Near misses: {}", stats.near_misses),
+                format!("Best multiplier: x{}", stats.best_multiplier),
+                format!("Time played: {}h {:02}m", minutes / 60, minutes % 60),
+            ]);
+        }
+        lines
+    }
+}
+
+impl Scene for ProfileScene {
+    fn update(&mut self, _args: UpdateArgs) {}
+
+    fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
+        let lines = self
+            .lines()
+            .iter()
+            .map(|line| self.font.create_text(line).unwrap())
+            .collect();
+
+        gl.draw(args.viewport(), |c, g| {
+            use graphics::clear;
+
+            clear([0.0; 4], g);
+
+            let center = {
+                let [_, _, width, height] = args.viewport().rect;
+                (width as f64 / 2.0, height as f64 / 2.0)
+            };
+
+            self.font
+                .render_lines(&lines, Layout::Centered, center, color::WHITE, &c, g)
+                .unwrap();
+        });
+    }
+
+    fn on_event(&mut self, event: Event) {
+        event.button(|args| {
+            on_hat_press(args, HatState::Down, || self.move_cursor(1));
+            on_hat_press(args, HatState::Up, || self.move_cursor(-1));
+            on_hat_press(args, HatState::Right, || {
+                if self.naming.is_some() {
+                    self.confirm();
+                }
+            });
+            on_hat_press(args, HatState::Left, || {
+                if self.naming.is_some() {
+                    self.back();
+                }
+            });
+            on_button_press(args, 0, || self.confirm());
+            on_button_press(args, 1, || self.back());
+        });
+    }
+}
@@ -1,9 +1,50 @@
-use crate::resource::score::Score;
+use crate::{
+    high_scores::Initials,
+    netplay::Outcome,
+    profile::NAME_LENGTH,
+    resource::{game_mode::GameMode, score::Score, scoring::Breakdown},
+    spectate::Ending,
+};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum SceneEvent {
     Quit,
-    GoToGame,
-    PlayerHit { current_score: Score },
+    /// Resume the suspended game.
+    Continue,
+    GoToGame {
+        mode: GameMode,
+    },
+    GameOver {
+        mode: GameMode,
+        current_score: Score,
+        breakdown: Breakdown,
+    },
+    /// The player has signed a score which made the high score table.
+    SignHighScore {
+        mode: GameMode,
+        initials: Initials,
+        current_score: Score,
+        breakdown: Breakdown,
+    },
+    /// A game of versus is over.
+    VersusOver {
+        outcome: Outcome,
+    },
+    /// A game being watched is over.
+    SpectatingOver {
+        ending: Ending,
+    },
     GoToTitle,
+    /// Show off the game while the title screen is idle.
+    GoToDemo,
+    GoToDaily,
+    GoToProfiles,
+    /// Switch the current profile between sprites and vector graphics.
+    ToggleGraphics,
+    SelectProfile {
+        index: usize,
+    },
+    CreateProfile {
+        name: [u8; NAME_LENGTH],
+    },
 }
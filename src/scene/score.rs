@@ -1,44 +1,96 @@
 use std::{
+    io,
     rc::Rc,
-    sync::{mpsc::Sender, Arc, Mutex},
+    sync::{
+        mpsc::{Receiver, Sender, TryRecvError},
+        Arc, Mutex,
+    },
 };
 
 use graphics::color;
 use opengl_graphics::GlGraphics;
 use piston::{Event, RenderArgs};
 
-use crate::{font::Font, resource::score::Score};
+use crate::{
+    font::Font,
+    leaderboard::{Leaderboard, Run},
+    resource::{game_mode::GameMode, score::Score, scoring::Breakdown},
+};
 
 use super::{scene_event::SceneEvent, Scene};
 
+/// Number of online scores to show.
+const ONLINE_COUNT: usize = 5;
+
+/// The best scores on the online leaderboard, as far as they are known.
+enum Online {
+    Disabled,
+    Loading(Receiver<io::Result<Vec<Run>>>),
+    Loaded(Vec<Run>),
+    Unavailable,
+}
+
 pub struct ScoreScene {
     bus: Arc<Mutex<Sender<SceneEvent>>>,
     font: Rc<Font>,
+    mode: GameMode,
     current_score: Score,
     high_score: Score,
+    breakdown: Breakdown,
+    /// Place earned in the high score table, if any.
+    rank: Option<usize>,
+    online: Online,
 }
 
 impl ScoreScene {
     pub fn new(
         bus: Arc<Mutex<Sender<SceneEvent>>>,
         font: Rc<Font>,
+        mode: GameMode,
         high_score: Score,
         current_score: Score,
+        breakdown: Breakdown,
+        rank: Option<usize>,
     ) -> Self {
         Self {
             bus,
             font,
+            mode,
             current_score,
             high_score,
+            breakdown,
+            rank,
+            online: Online::Disabled,
+        }
+    }
+
+    /// Shows the best scores from the online leaderboard, if there is one.
+    pub fn with_leaderboard(mut self, leaderboard: Option<&Leaderboard>) -> Self {
+        if let Some(leaderboard) = leaderboard {
+            self.online = Online::Loading(leaderboard.top(self.mode, ONLINE_COUNT));
         }
+        self
     }
 }
 
 impl Scene for ScoreScene {
-    fn update(&mut self, _args: piston::UpdateArgs) {}
+    fn update(&mut self, _args: piston::UpdateArgs) {
+        if let Online::Loading(receiver) = &self.online {
+            self.online = match receiver.try_recv() {
+                Ok(Ok(runs)) => Online::Loaded(runs),
+                Ok(Err(e)) => {
+                    log::warn!("Could not fetch online scores: {}", e);
+                    Online::Unavailable
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => Online::Unavailable,
+            };
+        }
+    }
 
     fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
-        let lines = if self.current_score > self.high_score {
+        let mut lines = vec![self.font.create_text(self.mode.name()).unwrap()];
+        lines.extend(if self.current_score > self.high_score {
             vec![
                 self.font
                     .create_text(&format!("New high score: {}", self.current_score))
@@ -54,7 +106,50 @@ impl Scene for ScoreScene {
                     .create_text(&format!("High score: {}", self.high_score))
                     .unwrap(),
             ]
+        });
+
+        if let Some(rank) = self.rank {
+            lines.push(
+                self.font
+                    .create_text(&format!("Ranked #{} in {}", rank, self.mode.name()))
+                    .unwrap(),
+            );
+        }
+
+        let breakdown = &self.breakdown;
+        for line in [
+            String::new(),
+            format!("Kills: {}", breakdown.kills),
+            format!("Kill points: {}", breakdown.base),
+            format!("Combo bonus: {}", breakdown.combo_bonus),
+            format!("Best multiplier: x{}", breakdown.best_multiplier),
+            format!(
+                "Near misses: {} (+{})",
+                breakdown.near_misses, breakdown.near_miss_bonus
+            ),
+        ] {
+            lines.push(self.font.create_text(&line).unwrap());
+        }
+
+        let online = match &self.online {
+            Online::Disabled => vec![],
+            Online::Loading(_) => vec!["Loading online scores...".to_owned()],
+            Online::Loaded(runs) if runs.is_empty() => vec!["No online scores yet".to_owned()],
+            Online::Loaded(runs) => std::iter::once("Online:".to_owned())
+                .chain(
+                    runs.iter()
+                        .enumerate()
+                        .map(|(i, run)| format!("{:>2}. {:<8} {}", i + 1, run.name, run.score)),
+                )
+                .collect(),
+            Online::Unavailable => vec!["Online scores unavailable".to_owned()],
         };
+        if !online.is_empty() {
+            lines.push(self.font.create_text("").unwrap());
+        }
+        for line in online {
+            lines.push(self.font.create_text(&line).unwrap());
+        }
 
         gl.draw(args.viewport(), |c, g| {
             use graphics::clear;
@@ -1,28 +1,44 @@
 use std::{
     rc::Rc,
     sync::{mpsc::Sender, Arc, Mutex},
+    time::Duration,
 };
 
 use graphics::color;
 use opengl_graphics::GlGraphics;
-use piston::{
-    Button, ButtonArgs, ButtonEvent, ButtonState, ControllerButton, ControllerHat, Event, HatState,
-    RenderArgs, UpdateArgs,
-};
+use piston::{ButtonEvent, Event, HatState, RenderArgs, UpdateArgs};
 
 use crate::{
     component::SpriteKind,
     font::{Font, Layout},
-    resource::textures::Textures,
+    high_scores::HighScores,
+    profile::{Graphics, Profile},
+    resource::{game_mode::GameMode, textures::Textures},
+};
+
+use super::{
+    menu::{on_button_press, on_hat_press, Menu},
+    GameScene, Scene, SceneEvent,
 };
 
-use super::{Scene, SceneEvent};
+/// How long the title screen sits untouched before showing a demo.
+const ATTRACT_DELAY: Duration = Duration::from_secs(30);
 
 pub struct TitleScene {
     textures: Rc<Textures>,
     font: Rc<Font>,
     bus: Arc<Mutex<Sender<SceneEvent>>>,
-    menu: Menu<2>,
+    menu: Menu<10>,
+    /// Whether there is a suspended game to continue. If not, "Continue" is
+    /// left out of the menu.
+    can_continue: bool,
+    profile_name: String,
+    high_scores: HighScores,
+    graphics: Graphics,
+    /// A message for the player, such as why a game could not be continued.
+    notice: Option<String>,
+    /// How long since a button was last pressed.
+    idle: Duration,
 }
 
 impl TitleScene {
@@ -30,40 +46,140 @@ impl TitleScene {
         bus: Arc<Mutex<Sender<SceneEvent>>>,
         textures: Rc<Textures>,
         font: Rc<Font>,
+        profile: &Profile,
     ) -> Self {
+        let can_continue = GameScene::has_save();
         Self {
             textures,
             font,
             bus,
+            profile_name: profile.name.clone(),
+            high_scores: profile.high_scores.clone(),
+            graphics: profile.graphics,
+            can_continue,
+            notice: None,
+            idle: Duration::ZERO,
             menu: Menu {
-                choices: [SceneEvent::GoToGame, SceneEvent::Quit],
-                cursor_index: 0,
+                choices: [
+                    ("Continue", SceneEvent::Continue),
+                    (
+                        "Play",
+                        SceneEvent::GoToGame {
+                            mode: GameMode::Classic,
+                        },
+                    ),
+                    (
+                        GameMode::TimeAttack.name(),
+                        SceneEvent::GoToGame {
+                            mode: GameMode::TimeAttack,
+                        },
+                    ),
+                    (
+                        GameMode::Survival.name(),
+                        SceneEvent::GoToGame {
+                            mode: GameMode::Survival,
+                        },
+                    ),
+                    (
+                        GameMode::Zen.name(),
+                        SceneEvent::GoToGame {
+                            mode: GameMode::Zen,
+                        },
+                    ),
+                    (
+                        "Versus CPU",
+                        SceneEvent::GoToGame {
+                            mode: GameMode::Versus,
+                        },
+                    ),
+                    ("Daily Challenge", SceneEvent::GoToDaily),
+                    ("Profiles", SceneEvent::GoToProfiles),
+                    (profile.graphics.label(), SceneEvent::ToggleGraphics),
+                    ("Quit", SceneEvent::Quit),
+                ],
+                cursor_index: if can_continue { 0 } else { 1 },
             },
         }
     }
 }
 
+impl TitleScene {
+    pub fn with_notice(self, notice: String) -> Self {
+        Self {
+            notice: Some(notice),
+            ..self
+        }
+    }
+
+    fn move_cursor_down(&mut self) {
+        self.menu.move_cursor_down();
+        if !self.can_continue && self.menu.selected() == SceneEvent::Continue {
+            self.menu.move_cursor_down();
+        }
+    }
+
+    fn move_cursor_up(&mut self) {
+        self.menu.move_cursor_up();
+        if !self.can_continue && self.menu.selected() == SceneEvent::Continue {
+            self.menu.move_cursor_up();
+        }
+    }
+
+    /// Shows the other graphics in the menu, while the profile is switched
+    /// over to them.
+    fn toggle_graphics(&mut self) {
+        self.graphics = self.graphics.toggled();
+        let label = self.graphics.label();
+        for choice in &mut self.menu.choices {
+            if choice.1 == SceneEvent::ToggleGraphics {
+                choice.0 = label;
+            }
+        }
+    }
+
+    /// The mode whose high scores are shown alongside the menu.
+    fn selected_mode(&self) -> GameMode {
+        match self.menu.selected() {
+            SceneEvent::GoToGame { mode } => mode,
+            SceneEvent::GoToDaily => GameMode::Daily,
+            _ => GameMode::Classic,
+        }
+    }
+}
+
 impl Scene for TitleScene {
-    fn update(&mut self, _args: UpdateArgs) {
-        // No-op
+    fn update(&mut self, args: UpdateArgs) {
+        self.idle += Duration::from_secs_f64(args.dt);
+        if self.idle >= ATTRACT_DELAY {
+            self.idle = Duration::ZERO;
+            self.bus.lock().unwrap().send(SceneEvent::GoToDemo).unwrap();
+        }
     }
 
     fn render(&mut self, args: RenderArgs, gl: &mut GlGraphics) {
-        use graphics::{clear, image, Transformed};
-
-        let texture = self.textures.get(&SpriteKind::Title).unwrap();
-
-        let lines = if self.menu.cursor_index == 0 {
-            vec![
-                self.font.create_text("> Play <").unwrap(),
-                self.font.create_text("Quit").unwrap(),
-            ]
-        } else {
-            vec![
-                self.font.create_text("Play").unwrap(),
-                self.font.create_text("> Quit <").unwrap(),
-            ]
-        };
+        use graphics::{clear, Transformed};
+
+        let lines = self
+            .menu
+            .labels()
+            .skip(if self.can_continue { 0 } else { 1 })
+            .map(|label| self.font.create_text(&label).unwrap())
+            .collect();
+        let notice = self
+            .notice
+            .iter()
+            .map(|notice| self.font.create_text(notice).unwrap())
+            .collect::<Vec<_>>();
+
+        let mode = self.selected_mode();
+        let mut table = vec![self.font.create_text(mode.name()).unwrap()];
+        table.extend(self.high_scores.table(mode).iter().map(|entry| {
+            self.font
+                .create_text(&format!("{} {}", entry.initials, entry.score))
+                .unwrap()
+        }));
+        let profile = vec![self.font.create_text(&self.profile_name).unwrap()];
+        let table_width = table.iter().map(|line| line.width).fold(0.0, f64::max);
 
         let [_, _, width, height] = args.viewport().rect;
         let center = (width as f64 / 2.0, height as f64 / 2.0);
@@ -71,8 +187,9 @@ impl Scene for TitleScene {
         gl.draw(args.viewport(), |c, g| {
             clear([0.0; 4], g);
 
-            image(
-                texture,
+            self.textures.draw(
+                &SpriteKind::Title,
+                &c.draw_state,
                 c.transform.trans(center.0 - 400.0, center.1 - 300.0),
                 g,
             );
@@ -80,79 +197,64 @@ impl Scene for TitleScene {
             self.font
                 .render_lines(&lines, Layout::Centered, center, color::WHITE, &c, g)
                 .unwrap();
+
+            self.font
+                .render_lines(
+                    &profile,
+                    Layout::Centered,
+                    (center.0, 30.0),
+                    color::WHITE,
+                    &c,
+                    g,
+                )
+                .unwrap();
+
+            if !notice.is_empty() {
+                self.font
+                    .render_lines(
+                        &notice,
+                        Layout::Centered,
+                        (center.0, height as f64 - 30.0),
+                        [1.0, 0.4, 0.4, 1.0],
+                        &c,
+                        g,
+                    )
+                    .unwrap();
+            }
+
+            // High scores for the selected mode, right of the menu.
+            self.font
+                .render_lines(
+                    &table,
+                    Layout::LeftAlign,
+                    (width as f64 - table_width / 2.0 - 10.0, center.1),
+                    color::grey(0.8),
+                    &c,
+                    g,
+                )
+                .unwrap();
         });
     }
 
     fn on_event(&mut self, event: Event) {
         event.button(|args| {
+            self.idle = Duration::ZERO;
+
             on_hat_press(args, HatState::Down, || {
-                self.menu.move_cursor_down();
+                self.move_cursor_down();
             });
 
             on_hat_press(args, HatState::Up, || {
-                self.menu.move_cursor_up();
+                self.move_cursor_up();
             });
 
             on_button_press(args, 0, || {
-                self.bus.lock().unwrap().send(self.menu.selected()).unwrap();
+                let selected = self.menu.selected();
+                if selected == SceneEvent::ToggleGraphics {
+                    self.toggle_graphics();
+                }
+                self.bus.lock().unwrap().send(selected).unwrap();
             });
         });
     }
 }
-
-fn on_hat_press<F>(args: ButtonArgs, state: HatState, callback: F)
-where
-    F: FnOnce() -> (),
-{
-    match args {
-        ButtonArgs {
-            // TODO: possible Piston bug: pressing a hat direction generates the
-            // Release event; releasing a hat does nothing.
-            state: ButtonState::Release,
-            button: Button::Hat(ControllerHat { state: s, .. }),
-            ..
-        } if s == state => callback(),
-        _ => (),
-    }
-}
-
-fn on_button_press<F>(args: ButtonArgs, button: u8, callback: F)
-where
-    F: FnOnce() -> (),
-{
-    match args {
-        ButtonArgs {
-            state: ButtonState::Press,
-            button: Button::Controller(ControllerButton { button: b, .. }),
-            ..
-        } if b == button => callback(),
-        _ => (),
-    }
-}
-
-#[derive(Copy, Clone)]
-struct Menu<const L: usize>
-where
-    [SceneEvent; L]: Copy,
-{
-    choices: [SceneEvent; L],
-    cursor_index: usize,
-}
-
-impl<const L: usize> Menu<L> {
-    pub fn move_cursor_down(&mut self) {
-        self.cursor_index = (self.cursor_index + 1) % self.choices.len();
-    }
-
-    pub fn move_cursor_up(&mut self) {
-        if self.cursor_index == 0 {
-            self.cursor_index = self.choices.len() - 1;
-        } else {
-            self.cursor_index -= 1;
-        }
-    }
-
-    pub fn selected(&self) -> SceneEvent {
-        *self.choices.get(self.cursor_index).unwrap()
-    }
-}
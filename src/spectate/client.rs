@@ -0,0 +1,200 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufReader, ErrorKind},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    replay::{Input, Replay},
+    resource::score::Score,
+    scene::{SceneEvent, Simulation},
+};
+
+use super::{read, Ending, Message};
+
+/// Updates held back before the game is shown, so that it keeps playing
+/// smoothly while the next inputs are on their way.
+const BUFFER: u64 = 30;
+/// Updates simulated at once while catching up on a game joined part-way
+/// through, or after the stream stalled.
+const CATCH_UP: u32 = 8;
+
+/// Plays out a game broadcast from another machine, a little behind it.
+pub struct Spectating {
+    /// The conditions the game started under.
+    start: Replay,
+    messages: Receiver<Message>,
+    /// Events from the simulation, once it has started.
+    events: Option<Receiver<SceneEvent>>,
+    /// Inputs received but not yet simulated.
+    pending: VecDeque<Input>,
+    /// The number of updates pending.
+    buffered: u64,
+    is_buffering: bool,
+    /// Whether the broadcaster has said the game is over.
+    has_ended: bool,
+    has_hung_up: bool,
+}
+
+impl Spectating {
+    /// Connects to the broadcaster at the given address, waiting up to the
+    /// timeout for a game to start there.
+    pub fn connect(addr: impl ToSocketAddrs, timeout: Duration) -> io::Result<Self> {
+        let addr = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "no address to connect to"))?;
+        let stream = TcpStream::connect_timeout(&addr, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        let mut reader = BufReader::new(stream);
+
+        let mut start = match read(&mut reader)? {
+            Some(Message::Start(replay)) => replay,
+            Some(_) => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "the broadcast did not begin at the start",
+                ))
+            }
+            None => {
+                return Err(io::Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "the broadcaster hung up",
+                ))
+            }
+        };
+        reader.get_ref().set_read_timeout(None)?;
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || loop {
+            match read(&mut reader) {
+                Ok(Some(message)) => {
+                    if sender.send(message).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => return,
+                Err(e) => {
+                    log::warn!("Could not follow the broadcast: {}", e);
+                    return;
+                }
+            }
+        });
+
+        let pending: VecDeque<_> = start.inputs.drain(..).collect();
+        Ok(Self {
+            start,
+            messages,
+            events: None,
+            buffered: pending.iter().map(|input| input.ticks as u64).sum(),
+            pending,
+            is_buffering: true,
+            has_ended: false,
+            has_hung_up: false,
+        })
+    }
+
+    /// Starts playing out the game being watched.
+    pub fn simulation(&mut self) -> Simulation {
+        // The game is over when the broadcaster's is, so the simulation's
+        // events are only watched for the final score.
+        let (sender, events) = mpsc::channel();
+        self.events = Some(events);
+        Simulation::new(
+            self.start.bounds,
+            self.start.tuning,
+            self.start.mode,
+            self.start.seed,
+            Arc::new(Mutex::new(sender)),
+        )
+    }
+
+    /// Whether the game is held up waiting for the broadcast.
+    pub fn is_buffering(&self) -> bool {
+        self.is_buffering
+    }
+
+    /// Plays out the next update, if it has arrived, catching up when far
+    /// behind. Returns how the game ended once it has.
+    pub fn advance(&mut self, sim: &mut Simulation) -> Option<Ending> {
+        self.receive();
+
+        if self.is_buffering {
+            if self.buffered < BUFFER && !self.has_ended && !self.has_hung_up {
+                return None;
+            }
+            self.is_buffering = false;
+        }
+
+        let steps = if self.buffered > 2 * BUFFER {
+            CATCH_UP
+        } else {
+            1
+        };
+        for _ in 0..steps {
+            let input = match self.pending.front_mut() {
+                Some(input) => input,
+                None => break,
+            };
+            sim.step(self.start.timestep, &input.controllers);
+            input.ticks -= 1;
+            if input.ticks == 0 {
+                self.pending.pop_front();
+            }
+            self.buffered -= 1;
+
+            if let Some(score) = self.game_over() {
+                return Some(Ending::Over(score));
+            }
+        }
+
+        if self.buffered == 0 {
+            if self.has_ended {
+                return Some(Ending::Left);
+            }
+            if self.has_hung_up {
+                return Some(Ending::Lost);
+            }
+            self.is_buffering = true;
+        }
+        None
+    }
+
+    /// Takes in everything the broadcaster has sent.
+    fn receive(&mut self) {
+        loop {
+            match self.messages.try_recv() {
+                Ok(Message::Inputs(inputs)) => {
+                    for input in inputs {
+                        self.buffered += input.ticks as u64;
+                        self.pending.push_back(input);
+                    }
+                }
+                Ok(Message::End) => self.has_ended = true,
+                Ok(Message::Start(_)) => log::warn!("The broadcast started over"),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.has_hung_up = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// The final score, if the game has ended.
+    fn game_over(&self) -> Option<Score> {
+        self.events
+            .as_ref()?
+            .try_iter()
+            .find_map(|event| match event {
+                SceneEvent::GameOver { current_score, .. } => Some(current_score),
+                _ => None,
+            })
+    }
+}
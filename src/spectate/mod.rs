@@ -0,0 +1,189 @@
+mod client;
+mod server;
+
+use std::io::{self, BufRead, ErrorKind, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    replay::{Input, Replay},
+    resource::score::Score,
+};
+
+pub use client::Spectating;
+pub use server::{Broadcast, Broadcaster};
+
+/// What a broadcaster tells its spectators, one message per line. Since the
+/// game plays out the same way everywhere given the same input, spectators are
+/// only sent the input, and play the game out for themselves.
+#[derive(Serialize, Deserialize)]
+pub enum Message {
+    /// The game so far, sent to each spectator as they join.
+    Start(Replay),
+    /// The updates since the last message.
+    Inputs(Vec<Input>),
+    /// The game is over.
+    End,
+}
+
+/// How a game being watched ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ending {
+    Over(Score),
+    /// The player left the game before it was over.
+    Left,
+    /// The broadcaster stopped answering.
+    Lost,
+}
+
+impl Ending {
+    pub fn message(&self) -> String {
+        match self {
+            Ending::Over(score) => format!("Game over! Final score: {}", score),
+            Ending::Left => "The player left the game".to_owned(),
+            Ending::Lost => "Lost contact with the broadcast".to_owned(),
+        }
+    }
+}
+
+fn write(stream: &mut impl Write, message: &Message) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stream.write_all(&line)
+}
+
+/// Reads the next message, or None once the broadcaster hangs up.
+fn read(stream: &mut impl BufRead) -> io::Result<Option<Message>> {
+    let mut line = String::new();
+    if stream.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{SocketAddr, TcpListener},
+        sync::{mpsc, Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        bot::TIMESTEP,
+        controller::ControllerState,
+        resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+        scene::Simulation,
+    };
+
+    use super::*;
+
+    /// Updates played before and after the second spectator joins.
+    const TICKS: u64 = 180;
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn free_address() -> SocketAddr {
+        TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    fn controller(tick: u64) -> ControllerState {
+        ControllerState {
+            left_thumb: if (tick / 20).is_multiple_of(2) {
+                (1.0, 0.0)
+            } else {
+                (0.0, -1.0)
+            },
+            right_thumb: (1.0, 0.0),
+            switch_weapon: false,
+            fire: tick.is_multiple_of(3),
+        }
+    }
+
+    /// Plays the broadcast game forward, feeding each update to spectators.
+    fn play(sim: &mut Simulation, broadcast: &mut Broadcast, ticks: u64) {
+        for _ in 0..ticks {
+            let tick = sim.replay.ticks();
+            sim.step(TIMESTEP, &[controller(tick)]);
+            broadcast.update(&sim.replay);
+        }
+    }
+
+    /// Plays out the game being watched until it ends.
+    fn watch(mut spectating: Spectating) -> (Simulation, Ending) {
+        let mut sim = spectating.simulation();
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            if let Some(ending) = spectating.advance(&mut sim) {
+                return (sim, ending);
+            }
+            assert!(Instant::now() < deadline, "the broadcast never ended");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn spectators_watch_the_same_game() {
+        let address = free_address();
+        let broadcaster = Broadcaster::bind(address).unwrap();
+
+        // One spectator waits for the game to start, another joins part-way.
+        let early = thread::spawn(move || Spectating::connect(address, TIMEOUT).unwrap());
+        thread::sleep(Duration::from_millis(200));
+
+        let (sender, _events) = mpsc::channel();
+        let mut sim = Simulation::new(
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+            Tuning::new(),
+            GameMode::Zen,
+            42,
+            Arc::new(Mutex::new(sender)),
+        );
+        let mut broadcast = broadcaster.broadcast();
+        play(&mut sim, &mut broadcast, TICKS);
+        let late = Spectating::connect(address, TIMEOUT).unwrap();
+        play(&mut sim, &mut broadcast, TICKS);
+        drop(broadcast);
+
+        for spectating in [early.join().unwrap(), late] {
+            let (watched, ending) = watch(spectating);
+            assert_eq!(ending, Ending::Left);
+            assert_eq!(watched.replay.ticks(), 2 * TICKS);
+            assert_eq!(watched.replay.hash(), sim.replay.hash());
+            assert_eq!(watched.score(), sim.score());
+        }
+    }
+
+    #[test]
+    fn messages_survive_the_stream() {
+        let mut replay = Replay::new(
+            7,
+            GameMode::Classic,
+            Tuning::new(),
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+        );
+        replay.record(TIMESTEP, &[ControllerState::new()]);
+
+        let mut stream = Vec::new();
+        write(&mut stream, &Message::Start(replay.clone())).unwrap();
+        write(&mut stream, &Message::Inputs(replay.since(0))).unwrap();
+        write(&mut stream, &Message::End).unwrap();
+
+        let mut reader = stream.as_slice();
+        match read(&mut reader).unwrap() {
+            Some(Message::Start(start)) => assert_eq!(start.hash(), replay.hash()),
+            _ => panic!("expected the start of the game"),
+        }
+        match read(&mut reader).unwrap() {
+            Some(Message::Inputs(inputs)) => assert_eq!(inputs.len(), 1),
+            _ => panic!("expected inputs"),
+        }
+        assert!(matches!(read(&mut reader).unwrap(), Some(Message::End)));
+        assert!(read(&mut reader).unwrap().is_none());
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::replay::{Input, Replay};
+
+use super::{write, Message};
+
+/// How often new inputs are sent to spectators. Sending them in batches keeps
+/// the stream small, at the cost of spectators lagging a little further
+/// behind.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a spectator may hold up the broadcast before they are let go.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What the game tells the broadcaster.
+enum Feed {
+    Start(Replay),
+    Inputs(Vec<Input>),
+    End,
+}
+
+/// Lets spectators watch the games played at this machine. Spectators may
+/// connect at any time; those joining part-way through a game are sent the
+/// game so far to catch up on.
+pub struct Broadcaster {
+    feed: Sender<Feed>,
+}
+
+impl Broadcaster {
+    /// Listens for spectators at the given address.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        log::info!("Broadcasting to spectators at {}", listener.local_addr()?);
+
+        let (feed, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut game = Game {
+                replay: None,
+                spectators: Vec::new(),
+                pending: Vec::new(),
+            };
+            let mut flushed = Instant::now();
+            loop {
+                match receiver.recv_timeout(FLUSH_INTERVAL) {
+                    Ok(Feed::Start(replay)) => {
+                        game.broadcast(&Message::Start(replay.clone()));
+                        game.replay = Some(replay);
+                        game.pending.clear();
+                    }
+                    Ok(Feed::Inputs(inputs)) => game.pending.extend(inputs),
+                    Ok(Feed::End) => {
+                        game.flush();
+                        game.broadcast(&Message::End);
+                        // Spectators are let go along with the game.
+                        game.spectators.clear();
+                        game.replay = None;
+                    }
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+
+                if flushed.elapsed() >= FLUSH_INTERVAL {
+                    game.flush();
+                    game.accept(&listener);
+                    flushed = Instant::now();
+                }
+            }
+        });
+
+        Ok(Self { feed })
+    }
+
+    /// A feed for the game about to start.
+    pub fn broadcast(&self) -> Broadcast {
+        Broadcast {
+            feed: self.feed.clone(),
+            sent: None,
+        }
+    }
+}
+
+/// Feeds one game to the broadcaster. The game is over for spectators once
+/// this is dropped.
+pub struct Broadcast {
+    feed: Sender<Feed>,
+    /// The number of updates sent so far, once the game has started.
+    sent: Option<u64>,
+}
+
+impl Broadcast {
+    /// Sends whatever has been recorded since the last update.
+    pub fn update(&mut self, replay: &Replay) {
+        let feed = match self.sent {
+            // Spectators need to know how long an update is, which isn't known
+            // until one has been recorded.
+            None if replay.ticks() == 0 => return,
+            None => Feed::Start(replay.clone()),
+            Some(sent) if sent == replay.ticks() => return,
+            Some(sent) => Feed::Inputs(replay.since(sent)),
+        };
+        self.sent = Some(replay.ticks());
+        // The broadcaster only stops once every feed has gone.
+        self.feed.send(feed).unwrap();
+    }
+}
+
+impl Drop for Broadcast {
+    fn drop(&mut self) {
+        if self.sent.is_some() {
+            self.feed.send(Feed::End).unwrap();
+        }
+    }
+}
+
+/// The game being broadcast, as the broadcaster's thread sees it.
+struct Game {
+    /// The game so far, up to the last inputs sent to spectators.
+    replay: Option<Replay>,
+    spectators: Vec<TcpStream>,
+    /// Inputs yet to be sent.
+    pending: Vec<Input>,
+}
+
+impl Game {
+    /// Sends the pending inputs to every spectator.
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let inputs = self.pending.split_off(0);
+        if let Some(replay) = &mut self.replay {
+            replay.extend(inputs.iter().cloned());
+        }
+        self.broadcast(&Message::Inputs(inputs));
+    }
+
+    /// Takes in anyone waiting to watch, catching them up on the game in
+    /// progress.
+    fn accept(&mut self, listener: &TcpListener) {
+        loop {
+            let (stream, addr) = match listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) => {
+                    log::warn!("Could not accept a spectator: {}", e);
+                    return;
+                }
+            };
+
+            log::info!("Spectator joined from {}", addr);
+            let joined = stream
+                .set_nonblocking(false)
+                .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+                .and_then(|_| match &self.replay {
+                    Some(replay) => write(&mut &stream, &Message::Start(replay.clone())),
+                    None => Ok(()),
+                });
+            match joined {
+                Ok(()) => self.spectators.push(stream),
+                Err(e) => log::info!("Spectator left: {}", e),
+            }
+        }
+    }
+
+    /// Sends a message to every spectator, letting go of any who have left.
+    fn broadcast(&mut self, message: &Message) {
+        self.spectators
+            .retain(|stream| match write(&mut &*stream, message) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::info!("Spectator left: {}", e);
+                    false
+                }
+            });
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The directory in which Rust Rocks keeps its files, such as
+/// `~/.local/share/rust-rocks` on Linux.
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("rust-rocks")
+}
+
+/// Loads a value from the named file in the data directory, or None if the
+/// file does not exist.
+pub fn load<T: DeserializeOwned>(name: &str) -> io::Result<Option<T>> {
+    let bytes = match fs::read(data_dir().join(name)) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Saves a value to the named file in the data directory. The name may include
+/// subdirectories, which are created as needed. The value is written to a
+/// temporary file which then replaces the original, so a crash part-way
+/// through never leaves a half-written file behind.
+pub fn save<T: Serialize>(name: &str, value: &T) -> io::Result<()> {
+    let dir = data_dir();
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap_or(&dir))?;
+
+    let bytes = serde_json::to_vec_pretty(value)?;
+    let tmp = dir.join(format!("{}.tmp", name));
+    fs::write(&tmp, bytes)?;
+    fs::rename(tmp, path)
+}
+
+/// Loads a value from the named file in the data directory, falling back to
+/// the default if the file does not exist or cannot be read. A corrupt file is
+/// set aside rather than standing in the way of a fresh start.
+pub fn load_or_default<T: DeserializeOwned + Default>(name: &str) -> T {
+    match load(name) {
+        Ok(value) => value.unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Could not load {}: {}", name, e);
+            if e.kind() == ErrorKind::InvalidData {
+                if let Err(e) = set_aside(name) {
+                    log::error!("Could not set aside {}: {}", name, e);
+                }
+            }
+            T::default()
+        }
+    }
+}
+
+/// Moves an unreadable file aside as `<name>.corrupt`, so that a fresh file can
+/// take its place without losing whatever was left in the old one.
+pub fn set_aside(name: &str) -> io::Result<()> {
+    let dir = data_dir();
+    fs::rename(dir.join(name), dir.join(format!("{}.corrupt", name)))
+}
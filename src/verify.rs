@@ -0,0 +1,283 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+};
+
+use crate::{
+    leaderboard::Run,
+    replay::{Replay, REPLAY_VERSION},
+    resource::score::Score,
+    scene::{SceneEvent, Simulation},
+};
+
+/// How a replay played out.
+#[derive(Clone, Copy, Debug)]
+pub struct Outcome {
+    pub score: Score,
+    /// The update on which the game ended, or the number of updates recorded
+    /// if it never did.
+    pub ticks: u64,
+    /// Whether the game ended by itself, rather than the replay running out.
+    pub is_over: bool,
+}
+
+/// Why a claimed run could not be verified.
+#[derive(Clone, Debug)]
+pub enum Mismatch {
+    /// The replay was recorded by another version of Rust Rocks.
+    Version(u32),
+    /// The claim is for some other replay.
+    Replay,
+    /// The player had help, such as from the autopilot or a bot.
+    Assisted,
+    /// The replay ran out before the game was over.
+    Unfinished {
+        ticks: u64,
+    },
+    Score {
+        claimed: Score,
+        actual: Score,
+    },
+    Ticks {
+        claimed: u64,
+        actual: u64,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Version(version) => write!(
+                f,
+                "the replay is in format {}, but only format {} can be played",
+                version, REPLAY_VERSION
+            ),
+            Mismatch::Replay => write!(f, "the claim does not belong to this replay"),
+            Mismatch::Assisted => write!(f, "the player was assisted"),
+            Mismatch::Unfinished { ticks } => {
+                write!(f, "the game was still going after {} updates", ticks)
+            }
+            Mismatch::Score { claimed, actual } => {
+                write!(f, "claimed a score of {}, but scored {}", claimed, actual)
+            }
+            Mismatch::Ticks { claimed, actual } => write!(
+                f,
+                "claimed the game ended on update {}, but it ended on update {}",
+                claimed, actual
+            ),
+        }
+    }
+}
+
+impl Error for Mismatch {}
+
+/// Plays a replay back headlessly, through the same systems as the game
+/// itself, until the game ends or the replay runs out.
+pub fn simulate(replay: &Replay) -> Outcome {
+    let (sender, events) = mpsc::channel();
+    let mut sim = Simulation::new(
+        replay.bounds,
+        replay.tuning,
+        replay.mode,
+        replay.seed,
+        Arc::new(Mutex::new(sender)),
+    );
+
+    let mut ticks = 0;
+    for input in &replay.inputs {
+        for _ in 0..input.ticks {
+            sim.step(replay.timestep, &input.controllers);
+            ticks += 1;
+            if let Some(score) = game_over(&events) {
+                return Outcome {
+                    score,
+                    ticks,
+                    is_over: true,
+                };
+            }
+        }
+    }
+
+    Outcome {
+        score: sim.score(),
+        ticks,
+        is_over: false,
+    }
+}
+
+/// Checks a claimed run against its replay, which must reproduce the claimed
+/// score and end on the claimed update.
+pub fn verify(replay: &Replay, claim: &Run) -> Result<Outcome, Mismatch> {
+    if replay.version != REPLAY_VERSION {
+        return Err(Mismatch::Version(replay.version));
+    }
+    if claim.replay != replay.hash() || claim.seed != replay.seed || claim.mode != replay.mode {
+        return Err(Mismatch::Replay);
+    }
+    if replay.assisted {
+        return Err(Mismatch::Assisted);
+    }
+
+    let outcome = simulate(replay);
+    // Only a game which the player may finish at will can end without the
+    // simulation ending it.
+    if !outcome.is_over && !replay.mode.can_finish() {
+        return Err(Mismatch::Unfinished {
+            ticks: outcome.ticks,
+        });
+    }
+    if outcome.ticks != claim.ticks {
+        return Err(Mismatch::Ticks {
+            claimed: claim.ticks,
+            actual: outcome.ticks,
+        });
+    }
+    if outcome.score != claim.score {
+        return Err(Mismatch::Score {
+            claimed: claim.score,
+            actual: outcome.score,
+        });
+    }
+
+    Ok(outcome)
+}
+
+/// The final score, if the game has ended.
+fn game_over(events: &Receiver<SceneEvent>) -> Option<Score> {
+    events.try_iter().find_map(|event| match event {
+        SceneEvent::GameOver { current_score, .. } => Some(current_score),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        autopilot::Autopilot,
+        bot::{Observation, TIMESTEP},
+        controller::ControllerState,
+        resource::{bounds::Bounds, game_mode::GameMode, tuning::Tuning},
+    };
+
+    use super::*;
+
+    /// The longest any recorded game is played for.
+    const MAX_TICKS: u64 = 20_000;
+
+    /// Plays a game until it is over or for the given number of updates, and
+    /// claims the run it made. The
+    /// replay is sent through JSON, as it would be to a leaderboard.
+    fn record(mode: GameMode, seed: u64, ticks: u64, pilot: Option<Autopilot>) -> (Replay, Run) {
+        let (sender, events) = mpsc::channel();
+        let mut sim = Simulation::new(
+            Bounds::new(0.0, 0.0, 800.0, 600.0),
+            Tuning::new(),
+            mode,
+            seed,
+            Arc::new(Mutex::new(sender)),
+        );
+
+        let mut score = None;
+        while score.is_none() && sim.replay.ticks() < ticks {
+            let controller = match &pilot {
+                Some(pilot) => pilot.control(&Observation::of(&sim)),
+                None => ControllerState::new(),
+            };
+            sim.step(TIMESTEP, &[controller]);
+            score = game_over(&events);
+        }
+
+        let json = serde_json::to_vec(&sim.replay).unwrap();
+        let replay: Replay = serde_json::from_slice(&json).unwrap();
+        let run = Run::new("Player", score.unwrap_or_else(|| sim.score()), &replay);
+        (replay, run)
+    }
+
+    #[test]
+    fn honest_runs_verify() {
+        let runs = [
+            record(GameMode::Classic, 1, MAX_TICKS, None),
+            record(GameMode::Survival, 2, MAX_TICKS, None),
+            record(GameMode::Zen, 3, 900, Some(Autopilot::new(0))),
+        ];
+        for (replay, run) in &runs {
+            let outcome = verify(replay, run).unwrap();
+            assert_eq!(outcome.score, run.score);
+            assert_eq!(outcome.ticks, run.ticks);
+        }
+    }
+
+    #[test]
+    fn tampered_claims_do_not_verify() {
+        let (replay, run) = record(GameMode::Classic, 1, MAX_TICKS, None);
+
+        let claim = Run {
+            score: Score::from(run.score.points() + 100),
+            ..run.clone()
+        };
+        assert!(matches!(
+            verify(&replay, &claim),
+            Err(Mismatch::Score { actual, .. }) if actual == run.score
+        ));
+
+        let claim = Run {
+            ticks: run.ticks + 1,
+            ..run.clone()
+        };
+        assert!(matches!(
+            verify(&replay, &claim),
+            Err(Mismatch::Ticks { actual, .. }) if actual == run.ticks
+        ));
+
+        let claim = Run {
+            seed: run.seed + 1,
+            ..run.clone()
+        };
+        assert!(matches!(verify(&replay, &claim), Err(Mismatch::Replay)));
+
+        let claim = Run {
+            replay: "0000000000000000".to_owned(),
+            ..run.clone()
+        };
+        assert!(matches!(verify(&replay, &claim), Err(Mismatch::Replay)));
+    }
+
+    #[test]
+    fn tampered_replays_do_not_verify() {
+        let (replay, run) = record(GameMode::Zen, 3, 600, Some(Autopilot::new(0)));
+
+        let old = Replay {
+            version: REPLAY_VERSION - 1,
+            ..replay.clone()
+        };
+        assert!(matches!(
+            verify(&old, &run),
+            Err(Mismatch::Version(version)) if version == REPLAY_VERSION - 1
+        ));
+
+        // Changing the inputs changes the hash the claim was made against.
+        let mut edited = replay.clone();
+        edited.inputs[0].controllers[0].fire = !edited.inputs[0].controllers[0].fire;
+        assert!(matches!(verify(&edited, &run), Err(Mismatch::Replay)));
+
+        let assisted = Replay {
+            assisted: true,
+            ..replay
+        };
+        let claim = Run::new("Player", run.score, &assisted);
+        assert!(matches!(verify(&assisted, &claim), Err(Mismatch::Assisted)));
+    }
+
+    #[test]
+    fn unfinished_games_do_not_verify() {
+        let (replay, run) = record(GameMode::Classic, 1, 300, Some(Autopilot::new(0)));
+        assert!(matches!(
+            verify(&replay, &run),
+            Err(Mismatch::Unfinished { ticks: 300 })
+        ));
+    }
+}